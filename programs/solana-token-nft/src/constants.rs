@@ -25,4 +25,43 @@ pub const EDITION_SEED: &[u8] = b"edition";
 pub const TOKEN_COUNT_SEED: &[u8] = b"token_count";
 
 #[constant]
-pub const COLLECTION_INFO: &[u8] = b"collection_info";
\ No newline at end of file
+pub const COLLECTION_INFO: &[u8] = b"collection_info";
+
+#[constant]
+pub const VOUCHER_SEED: &[u8] = b"voucher";
+
+#[constant]
+pub const WALLET_MINT_SEED: &[u8] = b"wallet_mint";
+
+#[constant]
+pub const MAX_MINT_PHASES: usize = 4;
+
+#[constant]
+pub const MAX_CREATORS: usize = 4;
+
+#[constant]
+pub const AUTH_RULES_PROGRAM_ID: Pubkey = pubkey!("auth9SigNpDKz4sJJ1DfCTuZrZNSAgh9sFD3rboVmgg");
+
+#[constant]
+pub const AIRDROP_MINT_SEED: &[u8] = b"airdrop_mint";
+
+// every item adds 5 accounts, a legacy transaction only fits 2 items so full batches
+// have to be sent as versioned transactions loading the accounts from a lookup table
+#[constant]
+pub const MAX_BATCH_MINT: usize = 5;
+
+#[constant]
+pub const TRAITS_SEED: &[u8] = b"traits";
+
+#[constant]
+pub const OFFER_SEED: &[u8] = b"offer";
+
+#[constant]
+pub const AUCTION_SEED: &[u8] = b"auction";
+
+// bids placed this many seconds before the end push it back to now + window
+#[constant]
+pub const AUCTION_EXTENSION_WINDOW: i64 = 300;
+
+#[constant]
+pub const COLLECTION_BID_SEED: &[u8] = b"collection_bid";
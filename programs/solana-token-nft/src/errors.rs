@@ -7,10 +7,136 @@ pub enum MarketPlaceError {
 
     #[msg("Insufficient balance !")]
     InsufficientBalance,
+
+    #[msg("Item is already listed !")]
+    ItemAlreadyListed,
+
+    #[msg("Invalid price !")]
+    InvalidPrice,
+
+    #[msg("Invalid fee !")]
+    InvalidFee,
+
+    #[msg("Invalid creator account !")]
+    InvalidCreator,
+
+    #[msg("Invalid auction end time !")]
+    InvalidEndTime,
+
+    #[msg("Auction has ended !")]
+    AuctionEnded,
+
+    #[msg("Auction has not ended !")]
+    AuctionNotEnded,
+
+    #[msg("Bid is too low !")]
+    BidTooLow,
+
+    #[msg("Invalid bidder account !")]
+    InvalidBidder,
+
+    #[msg("Invalid quantity !")]
+    InvalidQuantity,
+
+    #[msg("Invalid payment mint !")]
+    InvalidPaymentMint,
 }
 #[error_code]
 pub enum SigError {
     #[msg("Signature verification failed.")]
     SigVerificationFailed,
+
+    #[msg("Voucher signer is not set.")]
+    VoucherSignerNotSet,
+
+    #[msg("Voucher has expired.")]
+    VoucherExpired,
+
+    #[msg("Voucher quantity exceeded.")]
+    VoucherQuantityExceeded,
 }
 
+
+#[error_code]
+pub enum CollectionError {
+    #[msg("Unauthorized collection authority !")]
+    Unauthorized,
+
+    #[msg("Nft does not belong to the collection !")]
+    InvalidCollection,
+
+    #[msg("Collection max supply reached !")]
+    MaxSupplyReached,
+
+    #[msg("Max supply is below the minted count !")]
+    InvalidMaxSupply,
+
+    #[msg("Merkle root is not set !")]
+    MerkleRootNotSet,
+
+    #[msg("Invalid merkle proof !")]
+    InvalidMerkleProof,
+
+    #[msg("Allocation exceeded !")]
+    AllocationExceeded,
+
+    #[msg("Invalid mint phases !")]
+    InvalidMintPhases,
+
+    #[msg("Mint phase is not active !")]
+    MintPhaseNotActive,
+
+    #[msg("Mint is closed !")]
+    MintClosed,
+
+    #[msg("Wallet mint limit reached !")]
+    WalletLimitReached,
+
+    #[msg("Invalid royalties !")]
+    InvalidRoyalties,
+
+    #[msg("Collection PDA is not the token delegate !")]
+    NotDelegated,
+
+    #[msg("Edition supply reached !")]
+    EditionSupplyReached,
+
+    #[msg("Invalid edition marker !")]
+    InvalidEditionMarker,
+
+    #[msg("Invalid authorization rule set !")]
+    InvalidRuleSet,
+
+    #[msg("Invalid uses !")]
+    InvalidUses,
+
+    #[msg("Nft has no uses left !")]
+    NoUsesLeft,
+
+    #[msg("Invalid batch mint accounts !")]
+    InvalidBatchAccounts,
+
+    #[msg("Invalid reveal config !")]
+    InvalidRevealConfig,
+
+    #[msg("Collection has no delayed reveal !")]
+    RevealNotEnabled,
+
+    #[msg("Nft is already revealed !")]
+    AlreadyRevealed,
+
+    #[msg("Invalid provenance proof !")]
+    InvalidProvenanceProof,
+
+    #[msg("Invalid dutch auction !")]
+    InvalidDutchAuction,
+
+    #[msg("Drop has not ended !")]
+    DropNotEnded,
+
+    #[msg("Rebate already claimed !")]
+    RebateClaimed,
+
+    #[msg("Drop is not settled !")]
+    DropNotSettled,
+}
@@ -0,0 +1,328 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{program::invoke, system_instruction};
+use anchor_spl::token;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::metadata::MetadataAccount;
+use anchor_spl::token::{Mint, Token, TokenAccount, Transfer};
+use crate::states::{auction, init};
+use crate::constants::{AUCTION_EXTENSION_WINDOW, AUCTION_SEED, COLLECTION_SEED, INIT_SEED, VAULT_SEED};
+use crate::errors::{CollectionError, MarketPlaceError};
+use crate::instructions::marketplace::{release_vault, split_sale};
+use crate::instructions::spl_token::find_metadata_account;
+
+pub fn create_auction(ctx: Context<CreateAuction>, collection_id: u64, reserve_price: u64, min_increment: u64, end_time: i64) -> Result<()> {
+    require!(reserve_price > 0, MarketPlaceError::InvalidPrice);
+    // a zero increment would let equal bids displace the top bidder back and forth for free
+    require!(min_increment > 0, MarketPlaceError::InvalidPrice);
+    require!(end_time > Clock::get()?.unix_timestamp, MarketPlaceError::InvalidEndTime);
+
+    let auction = &mut ctx.accounts.auction;
+    auction.collection_id = collection_id;
+    auction.mint = ctx.accounts.mint.key();
+    auction.seller = ctx.accounts.seller.key();
+    auction.reserve_price = reserve_price;
+    auction.min_increment = min_increment;
+    auction.end_time = end_time;
+    auction.top_bidder = Pubkey::default();
+    auction.top_bid = 0;
+
+    msg!("moving nft into the vault");
+
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.seller_token_account.to_account_info(),
+                to: ctx.accounts.vault.to_account_info(),
+                authority: ctx.accounts.seller.to_account_info(),
+            },
+        ),
+        1,
+    )?;
+
+    emit!(CreateAuctionEvent {
+        collection_id,
+        mint: ctx.accounts.mint.key(),
+        seller: ctx.accounts.seller.key(),
+        reserve_price,
+        min_increment,
+        end_time,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(collection_id: u64)]
+pub struct CreateAuction<'info> {
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    #[account(
+        seeds = [COLLECTION_SEED, collection_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub collection_mint: Box<Account<'info, Mint>>,
+
+    pub mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        address = find_metadata_account(&mint.key()).0,
+        constraint = metadata_account.collection.as_ref().is_some_and(|c| c.verified && c.key == collection_mint.key()) @ CollectionError::InvalidCollection,
+    )]
+    pub metadata_account: Box<Account<'info, MetadataAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = seller,
+    )]
+    pub seller_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = seller,
+        space = 8 + auction::Auction::INIT_SPACE,
+        seeds = [AUCTION_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub auction: Box<Account<'info, auction::Auction>>,
+
+    #[account(
+        init_if_needed,
+        payer = seller,
+        seeds = [VAULT_SEED, mint.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = auction,
+    )]
+    pub vault: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[event]
+pub struct CreateAuctionEvent {
+    pub collection_id: u64,
+    pub mint: Pubkey,
+    pub seller: Pubkey,
+    pub reserve_price: u64,
+    pub min_increment: u64,
+    pub end_time: i64,
+}
+
+// the previous top bidder is refunded from the escrow before the new bid is locked
+pub fn place_bid(ctx: Context<PlaceBid>, amount: u64) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let auction = &ctx.accounts.auction;
+    require!(now < auction.end_time, MarketPlaceError::AuctionEnded);
+
+    let min_bid = if auction.top_bid == 0 {
+        auction.reserve_price
+    } else {
+        auction.top_bid.checked_add(auction.min_increment).ok_or(MarketPlaceError::BidTooLow)?
+    };
+    require!(amount >= min_bid, MarketPlaceError::BidTooLow);
+    require!(ctx.accounts.bidder.lamports() >= amount, MarketPlaceError::InsufficientBalance);
+
+    if auction.top_bid > 0 {
+        let previous_bidder = ctx.accounts.previous_bidder.as_ref().ok_or(MarketPlaceError::InvalidBidder)?;
+        require_keys_eq!(previous_bidder.key(), auction.top_bidder, MarketPlaceError::InvalidBidder);
+
+        msg!("refunding previous bidder");
+
+        **ctx.accounts.auction.to_account_info().try_borrow_mut_lamports()? -= auction.top_bid;
+        **previous_bidder.try_borrow_mut_lamports()? += auction.top_bid;
+    }
+
+    msg!("escrowing bid");
+
+    invoke(
+        &system_instruction::transfer(&ctx.accounts.bidder.key(), &ctx.accounts.auction.key(), amount),
+        &[
+            ctx.accounts.bidder.to_account_info(),
+            ctx.accounts.auction.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+        ],
+    )?;
+
+    let auction = &mut ctx.accounts.auction;
+    auction.top_bidder = ctx.accounts.bidder.key();
+    auction.top_bid = amount;
+    if auction.end_time - now < AUCTION_EXTENSION_WINDOW {
+        auction.end_time = now + AUCTION_EXTENSION_WINDOW;
+    }
+
+    emit!(PlaceBidEvent {
+        collection_id: auction.collection_id,
+        mint: auction.mint,
+        bidder: auction.top_bidder,
+        amount,
+        end_time: auction.end_time,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct PlaceBid<'info> {
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+
+    /// CHECK: address
+    #[account(mut)]
+    pub previous_bidder: Option<UncheckedAccount<'info>>,
+
+    #[account(
+        mut,
+        seeds = [AUCTION_SEED, auction.mint.as_ref()],
+        bump,
+    )]
+    pub auction: Box<Account<'info, auction::Auction>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event]
+pub struct PlaceBidEvent {
+    pub collection_id: u64,
+    pub mint: Pubkey,
+    pub bidder: Pubkey,
+    pub amount: u64,
+    pub end_time: i64,
+}
+
+// anyone can settle once the auction ended, without bids the nft goes back to the seller.
+// remaining_accounts holds the metadata creators in order
+pub fn settle_auction<'info>(ctx: Context<'_, '_, 'info, 'info, SettleAuction<'info>>) -> Result<()> {
+    let auction = &ctx.accounts.auction;
+    require!(Clock::get()?.unix_timestamp >= auction.end_time, MarketPlaceError::AuctionNotEnded);
+
+    let top_bid = auction.top_bid;
+    let mut fee = 0;
+    let mut royalties = 0;
+    if top_bid > 0 {
+        msg!("paying platform fee, royalties and seller from the auction escrow");
+
+        let escrow = ctx.accounts.auction.to_account_info();
+        let split = split_sale(
+            top_bid,
+            ctx.accounts.init.fee_basis_points,
+            &ctx.accounts.metadata_account,
+            ctx.remaining_accounts,
+            &ctx.accounts.fee_vault.to_account_info(),
+            &ctx.accounts.seller.to_account_info(),
+            |to, lamports| {
+                **escrow.try_borrow_mut_lamports()? -= lamports;
+                **to.try_borrow_mut_lamports()? += lamports;
+                Ok(())
+            },
+        )?;
+        fee = split.fee;
+        royalties = split.royalties;
+    }
+
+    msg!("moving nft to the winner");
+
+    let mint = ctx.accounts.auction.mint;
+    let signer_seeds: &[&[&[u8]]] = &[&[AUCTION_SEED, mint.as_ref(), &[ctx.bumps.auction]]];
+    release_vault(
+        &ctx.accounts.token_program,
+        &ctx.accounts.vault,
+        ctx.accounts.winner_token_account.to_account_info(),
+        ctx.accounts.auction.to_account_info(),
+        ctx.accounts.seller.to_account_info(),
+        signer_seeds,
+    )?;
+
+    let auction = &ctx.accounts.auction;
+    emit!(SettleAuctionEvent {
+        collection_id: auction.collection_id,
+        mint: auction.mint,
+        seller: auction.seller,
+        winner: ctx.accounts.winner.key(),
+        price: top_bid,
+        fee,
+        royalties,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SettleAuction<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: address
+    #[account(mut)]
+    pub seller: UncheckedAccount<'info>,
+
+    /// CHECK: address
+    #[account(
+        constraint = winner.key() == if auction.top_bid > 0 { auction.top_bidder } else { auction.seller } @ MarketPlaceError::InvalidBidder,
+    )]
+    pub winner: UncheckedAccount<'info>,
+
+    pub mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [AUCTION_SEED, mint.key().as_ref()],
+        bump,
+        has_one = seller,
+        has_one = mint,
+        close = seller,
+    )]
+    pub auction: Box<Account<'info, auction::Auction>>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = mint,
+        associated_token::authority = winner,
+    )]
+    pub winner_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        address = find_metadata_account(&mint.key()).0,
+    )]
+    pub metadata_account: Box<Account<'info, MetadataAccount>>,
+
+    #[account(
+        seeds = [INIT_SEED],
+        bump,
+    )]
+    pub init: Box<Account<'info, init::Init>>,
+
+    /// CHECK: address
+    #[account(
+        mut,
+        address = init.vault,
+    )]
+    pub fee_vault: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event]
+pub struct SettleAuctionEvent {
+    pub collection_id: u64,
+    pub mint: Pubkey,
+    pub seller: Pubkey,
+    pub winner: Pubkey,
+    pub price: u64,
+    pub fee: u64,
+    pub royalties: u64,
+}
@@ -0,0 +1,245 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Allocate, Assign, CreateAccount, Transfer};
+use anchor_spl::{associated_token, token};
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::metadata::Metadata;
+use anchor_spl::token::{InitializeMint2, Mint, Token};
+use crate::states::{collection, token_count};
+use crate::constants::{AIRDROP_MINT_SEED, COLLECTION_INFO, COLLECTION_SEED, MAX_BATCH_MINT, TOKEN_COUNT_SEED};
+use crate::errors::CollectionError;
+use crate::instructions::spl_token::{create_collection_item, find_master_edition_account, find_metadata_account, next_token_number, CollectionItemAccounts, MintNftEvent};
+
+// recipient, mint, recipient ata, metadata and master edition for every item
+const BATCH_ITEM_ACCOUNTS: usize = 5;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct BatchMintItem {
+    // caller chosen id, the item mint is the PDA [AIRDROP_MINT_SEED, collection_id, id]
+    pub id: u64,
+    pub name: String,
+    pub uri: String,
+}
+
+// items whose metadata already exists are skipped, so a batch that failed partway can be resent as is.
+// each item costs roughly 150k compute units, callers should request the extra budget up front
+pub fn batch_mint_nft<'info>(ctx: Context<'_, '_, 'info, 'info, BatchMintNft<'info>>, collection_id: u64, symbol: String, items: Vec<BatchMintItem>) -> Result<()> {
+    require!(items.len() <= MAX_BATCH_MINT, CollectionError::InvalidBatchAccounts);
+    require!(ctx.remaining_accounts.len() == items.len() * BATCH_ITEM_ACCOUNTS, CollectionError::InvalidBatchAccounts);
+
+    let collection_id_bytes = collection_id.to_le_bytes();
+    let signer_seeds: &[&[&[u8]]] = &[&[COLLECTION_SEED, &collection_id_bytes, &[ctx.bumps.collection_mint]]];
+    let collection_mint_key = ctx.accounts.collection_mint.key();
+    let total = items.len() as u64;
+    let mut minted: u64 = 0;
+
+    for (item, item_accounts) in items.into_iter().zip(ctx.remaining_accounts.chunks(BATCH_ITEM_ACCOUNTS)) {
+        let recipient = &item_accounts[0];
+        let mint = &item_accounts[1];
+        let nft_account_to = &item_accounts[2];
+        let metadata_account = &item_accounts[3];
+        let master_edition = &item_accounts[4];
+
+        let item_id_bytes = item.id.to_le_bytes();
+        let (mint_key, mint_bump) = Pubkey::find_program_address(
+            &[AIRDROP_MINT_SEED, &collection_id_bytes, &item_id_bytes],
+            ctx.program_id,
+        );
+        require_keys_eq!(mint.key(), mint_key, CollectionError::InvalidBatchAccounts);
+        require_keys_eq!(
+            nft_account_to.key(),
+            associated_token::get_associated_token_address(recipient.key, &mint_key),
+            CollectionError::InvalidBatchAccounts
+        );
+        require_keys_eq!(metadata_account.key(), find_metadata_account(&mint_key).0, CollectionError::InvalidBatchAccounts);
+        require_keys_eq!(master_edition.key(), find_master_edition_account(&mint_key).0, CollectionError::InvalidBatchAccounts);
+
+        if !metadata_account.data_is_empty() {
+            msg!("skipping already minted item {}", item.id);
+            continue;
+        }
+
+        let token_number = next_token_number(&ctx.accounts.collection_info, &mut ctx.accounts.token_count)?;
+
+        msg!("creating airdrop mint for item {}", item.id);
+
+        let mint_signer_seeds: &[&[&[u8]]] = &[&[AIRDROP_MINT_SEED, &collection_id_bytes, &item_id_bytes, &[mint_bump]]];
+        create_mint_account(
+            &ctx.accounts.authority.to_account_info(),
+            mint,
+            &ctx.accounts.system_program.to_account_info(),
+            mint_signer_seeds,
+        )?;
+
+        token::initialize_mint2(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                InitializeMint2 { mint: mint.clone() },
+            ),
+            0,
+            &collection_mint_key,
+            Some(&collection_mint_key),
+        )?;
+
+        associated_token::create_idempotent(CpiContext::new(
+            ctx.accounts.associated_token_program.to_account_info(),
+            associated_token::Create {
+                payer: ctx.accounts.authority.to_account_info(),
+                associated_token: nft_account_to.clone(),
+                authority: recipient.clone(),
+                mint: mint.clone(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                token_program: ctx.accounts.token_program.to_account_info(),
+            },
+        ))?;
+
+        create_collection_item(
+            CollectionItemAccounts {
+                payer: ctx.accounts.authority.to_account_info(),
+                mint: mint.clone(),
+                token_account: nft_account_to.clone(),
+                metadata_account: metadata_account.clone(),
+                master_edition: master_edition.clone(),
+                collection_mint: ctx.accounts.collection_mint.to_account_info(),
+                collection_metadata_account: ctx.accounts.collection_metadata_account.to_account_info(),
+                collection_master_edition_account: ctx.accounts.collection_master_edition_account.to_account_info(),
+                token_program: ctx.accounts.token_program.to_account_info(),
+                metadata_program: ctx.accounts.metadata_program.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                rent: ctx.accounts.rent.to_account_info(),
+            },
+            &ctx.accounts.collection_info,
+            signer_seeds,
+            token_number,
+            item.name,
+            symbol.clone(),
+            item.uri,
+        )?;
+
+        emit!(MintNftEvent {
+            collection_id,
+            mint: mint_key,
+            owner: recipient.key(),
+            token_number,
+            price: 0,
+        });
+
+        minted += 1;
+    }
+
+    emit!(BatchMintNftEvent {
+        collection_id,
+        minted,
+        skipped: total - minted,
+    });
+
+    Ok(())
+}
+
+// creates the mint PDA like anchor's init does, an address pre-funded by someone else is topped up,
+// allocated and assigned instead of failing create_account forever
+fn create_mint_account<'info>(payer: &AccountInfo<'info>, mint: &AccountInfo<'info>, system_program: &AccountInfo<'info>, signer_seeds: &[&[&[u8]]]) -> Result<()> {
+    let rent = Rent::get()?.minimum_balance(Mint::LEN);
+    let lamports = mint.lamports();
+
+    if lamports == 0 {
+        return system_program::create_account(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                CreateAccount {
+                    from: payer.clone(),
+                    to: mint.clone(),
+                },
+                signer_seeds,
+            ),
+            rent,
+            Mint::LEN as u64,
+            &token::ID,
+        );
+    }
+
+    if lamports < rent {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                Transfer {
+                    from: payer.clone(),
+                    to: mint.clone(),
+                },
+            ),
+            rent - lamports,
+        )?;
+    }
+
+    system_program::allocate(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            Allocate { account_to_allocate: mint.clone() },
+            signer_seeds,
+        ),
+        Mint::LEN as u64,
+    )?;
+
+    system_program::assign(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            Assign { account_to_assign: mint.clone() },
+            signer_seeds,
+        ),
+        &token::ID,
+    )
+}
+
+#[derive(Accounts)]
+#[instruction(collection_id: u64)]
+pub struct BatchMintNft<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [COLLECTION_INFO, collection_id.to_le_bytes().as_ref()],
+        bump,
+        has_one = authority @ CollectionError::Unauthorized,
+    )]
+    pub collection_info: Box<Account<'info, collection::CollectionInfo>>,
+
+    #[account(
+        mut,
+        seeds = [COLLECTION_SEED, collection_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub collection_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [TOKEN_COUNT_SEED, collection_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub token_count: Box<Account<'info, token_count::TokenCount>>,
+
+    /// CHECK: address
+    #[account(
+        mut,
+        address = find_metadata_account(&collection_mint.key()).0
+    )]
+    pub collection_metadata_account: UncheckedAccount<'info>,
+
+    /// CHECK: address
+    #[account(
+        mut,
+        address = find_master_edition_account(&collection_mint.key()).0
+    )]
+    pub collection_master_edition_account: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub metadata_program: Program<'info, Metadata>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[event]
+pub struct BatchMintNftEvent {
+    pub collection_id: u64,
+    pub minted: u64,
+    pub skipped: u64,
+}
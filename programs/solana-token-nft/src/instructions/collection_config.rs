@@ -0,0 +1,504 @@
+use anchor_lang::prelude::*;
+use crate::states::{collection, token_count};
+use crate::constants::{COLLECTION_INFO, MAX_CREATORS, MAX_MINT_PHASES, TOKEN_COUNT_SEED};
+use crate::errors::{CollectionError, MarketPlaceError};
+
+pub(crate) fn validate_royalties(seller_fee_basis_points: u16, creators: &[collection::CreatorShare]) -> Result<()> {
+    require!(seller_fee_basis_points <= 10000, CollectionError::InvalidRoyalties);
+    require!(creators.len() <= MAX_CREATORS, CollectionError::InvalidRoyalties);
+    if !creators.is_empty() {
+        let total: u16 = creators.iter().map(|creator| creator.share as u16).sum();
+        require!(total == 100, CollectionError::InvalidRoyalties);
+    }
+
+    Ok(())
+}
+
+pub fn set_max_supply(ctx: Context<SetMaxSupply>, collection_id: u64, max_supply: u64) -> Result<()> {
+    require!(
+        max_supply == 0 || max_supply >= ctx.accounts.token_count.count,
+        CollectionError::InvalidMaxSupply
+    );
+
+    ctx.accounts.collection_info.max_supply = max_supply;
+
+    emit!(SetMaxSupplyEvent {
+        collection_id,
+        max_supply,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(collection_id: u64)]
+pub struct SetMaxSupply<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [COLLECTION_INFO, collection_id.to_le_bytes().as_ref()],
+        bump,
+        has_one = authority @ CollectionError::Unauthorized,
+    )]
+    pub collection_info: Box<Account<'info, collection::CollectionInfo>>,
+
+    #[account(
+        seeds = [TOKEN_COUNT_SEED, collection_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub token_count: Box<Account<'info, token_count::TokenCount>>,
+}
+
+#[event]
+pub struct SetMaxSupplyEvent {
+    pub collection_id: u64,
+    pub max_supply: u64,
+}
+
+pub fn set_mint_price(ctx: Context<SetMintPrice>, collection_id: u64, mint_price: u64) -> Result<()> {
+    ctx.accounts.collection_info.mint_price = mint_price;
+
+    emit!(SetMintPriceEvent {
+        collection_id,
+        mint_price,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(collection_id: u64)]
+pub struct SetMintPrice<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [COLLECTION_INFO, collection_id.to_le_bytes().as_ref()],
+        bump,
+        has_one = authority @ CollectionError::Unauthorized,
+    )]
+    pub collection_info: Box<Account<'info, collection::CollectionInfo>>,
+}
+
+#[event]
+pub struct SetMintPriceEvent {
+    pub collection_id: u64,
+    pub mint_price: u64,
+}
+
+pub fn set_voucher_signer(ctx: Context<SetVoucherSigner>, collection_id: u64, eth_address: [u8; 20]) -> Result<()> {
+    ctx.accounts.collection_info.voucher_signer = eth_address;
+
+    emit!(SetVoucherSignerEvent {
+        collection_id,
+        eth_address,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(collection_id: u64)]
+pub struct SetVoucherSigner<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [COLLECTION_INFO, collection_id.to_le_bytes().as_ref()],
+        bump,
+        has_one = authority @ CollectionError::Unauthorized,
+    )]
+    pub collection_info: Box<Account<'info, collection::CollectionInfo>>,
+}
+
+#[event]
+pub struct SetVoucherSignerEvent {
+    pub collection_id: u64,
+    pub eth_address: [u8; 20],
+}
+
+pub fn set_allowlist_signer(ctx: Context<SetAllowlistSigner>, collection_id: u64, signer: Pubkey) -> Result<()> {
+    ctx.accounts.collection_info.allowlist_signer = signer;
+
+    emit!(SetAllowlistSignerEvent {
+        collection_id,
+        signer,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(collection_id: u64)]
+pub struct SetAllowlistSigner<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [COLLECTION_INFO, collection_id.to_le_bytes().as_ref()],
+        bump,
+        has_one = authority @ CollectionError::Unauthorized,
+    )]
+    pub collection_info: Box<Account<'info, collection::CollectionInfo>>,
+}
+
+#[event]
+pub struct SetAllowlistSignerEvent {
+    pub collection_id: u64,
+    pub signer: Pubkey,
+}
+
+pub fn set_merkle_root(ctx: Context<SetMerkleRoot>, collection_id: u64, merkle_root: [u8; 32]) -> Result<()> {
+    ctx.accounts.collection_info.merkle_root = merkle_root;
+
+    emit!(SetMerkleRootEvent {
+        collection_id,
+        merkle_root,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(collection_id: u64)]
+pub struct SetMerkleRoot<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [COLLECTION_INFO, collection_id.to_le_bytes().as_ref()],
+        bump,
+        has_one = authority @ CollectionError::Unauthorized,
+    )]
+    pub collection_info: Box<Account<'info, collection::CollectionInfo>>,
+}
+
+#[event]
+pub struct SetMerkleRootEvent {
+    pub collection_id: u64,
+    pub merkle_root: [u8; 32],
+}
+
+pub fn set_mint_phases(ctx: Context<SetMintPhases>, collection_id: u64, phases: Vec<collection::MintPhase>) -> Result<()> {
+    require!(phases.len() <= MAX_MINT_PHASES, CollectionError::InvalidMintPhases);
+    for (i, phase) in phases.iter().enumerate() {
+        require!(phase.start_time < phase.end_time, CollectionError::InvalidMintPhases);
+        if i > 0 {
+            require!(phases[i - 1].end_time <= phase.start_time, CollectionError::InvalidMintPhases);
+        }
+    }
+
+    let collection_info = &mut ctx.accounts.collection_info;
+    collection_info.phases = phases.clone();
+    collection_info.phases_version += 1;
+
+    emit!(SetMintPhasesEvent {
+        collection_id,
+        phases,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(collection_id: u64)]
+pub struct SetMintPhases<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [COLLECTION_INFO, collection_id.to_le_bytes().as_ref()],
+        bump,
+        has_one = authority @ CollectionError::Unauthorized,
+    )]
+    pub collection_info: Box<Account<'info, collection::CollectionInfo>>,
+}
+
+#[event]
+pub struct SetMintPhasesEvent {
+    pub collection_id: u64,
+    pub phases: Vec<collection::MintPhase>,
+}
+
+pub fn set_royalties(ctx: Context<SetRoyalties>, collection_id: u64, seller_fee_basis_points: u16, creators: Vec<collection::CreatorShare>) -> Result<()> {
+    validate_royalties(seller_fee_basis_points, &creators)?;
+
+    let collection_info = &mut ctx.accounts.collection_info;
+    collection_info.seller_fee_basis_points = seller_fee_basis_points;
+    collection_info.creators = creators.clone();
+
+    emit!(SetRoyaltiesEvent {
+        collection_id,
+        seller_fee_basis_points,
+        creators,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(collection_id: u64)]
+pub struct SetRoyalties<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [COLLECTION_INFO, collection_id.to_le_bytes().as_ref()],
+        bump,
+        has_one = authority @ CollectionError::Unauthorized,
+    )]
+    pub collection_info: Box<Account<'info, collection::CollectionInfo>>,
+}
+
+#[event]
+pub struct SetRoyaltiesEvent {
+    pub collection_id: u64,
+    pub seller_fee_basis_points: u16,
+    pub creators: Vec<collection::CreatorShare>,
+}
+
+pub fn set_edition_max_supply(ctx: Context<SetEditionMaxSupply>, collection_id: u64, edition_max_supply: u64) -> Result<()> {
+    ctx.accounts.collection_info.edition_max_supply = edition_max_supply;
+
+    emit!(SetEditionMaxSupplyEvent {
+        collection_id,
+        edition_max_supply,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(collection_id: u64)]
+pub struct SetEditionMaxSupply<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [COLLECTION_INFO, collection_id.to_le_bytes().as_ref()],
+        bump,
+        has_one = authority @ CollectionError::Unauthorized,
+    )]
+    pub collection_info: Box<Account<'info, collection::CollectionInfo>>,
+}
+
+#[event]
+pub struct SetEditionMaxSupplyEvent {
+    pub collection_id: u64,
+    pub edition_max_supply: u64,
+}
+
+pub fn set_rule_set(ctx: Context<SetRuleSet>, collection_id: u64, rule_set: Option<Pubkey>) -> Result<()> {
+    ctx.accounts.collection_info.rule_set = rule_set;
+
+    emit!(SetRuleSetEvent {
+        collection_id,
+        rule_set,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(collection_id: u64)]
+pub struct SetRuleSet<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [COLLECTION_INFO, collection_id.to_le_bytes().as_ref()],
+        bump,
+        has_one = authority @ CollectionError::Unauthorized,
+    )]
+    pub collection_info: Box<Account<'info, collection::CollectionInfo>>,
+}
+
+#[event]
+pub struct SetRuleSetEvent {
+    pub collection_id: u64,
+    pub rule_set: Option<Pubkey>,
+}
+
+pub fn set_uses(ctx: Context<SetUses>, collection_id: u64, uses: Option<collection::ItemUses>) -> Result<()> {
+    // metaplex only accepts a single use for the Single method
+    if let Some(item_uses) = uses {
+        require!(item_uses.total > 0, CollectionError::InvalidUses);
+        require!(
+            item_uses.use_method != collection::ItemUseMethod::Single || item_uses.total == 1,
+            CollectionError::InvalidUses
+        );
+    }
+
+    ctx.accounts.collection_info.uses = uses;
+
+    emit!(SetUsesEvent {
+        collection_id,
+        uses,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(collection_id: u64)]
+pub struct SetUses<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [COLLECTION_INFO, collection_id.to_le_bytes().as_ref()],
+        bump,
+        has_one = authority @ CollectionError::Unauthorized,
+    )]
+    pub collection_info: Box<Account<'info, collection::CollectionInfo>>,
+}
+
+#[event]
+pub struct SetUsesEvent {
+    pub collection_id: u64,
+    pub uses: Option<collection::ItemUses>,
+}
+
+pub fn set_game_authority(ctx: Context<SetGameAuthority>, collection_id: u64, game_authority: Pubkey) -> Result<()> {
+    ctx.accounts.collection_info.game_authority = game_authority;
+
+    emit!(SetGameAuthorityEvent {
+        collection_id,
+        game_authority,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(collection_id: u64)]
+pub struct SetGameAuthority<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [COLLECTION_INFO, collection_id.to_le_bytes().as_ref()],
+        bump,
+        has_one = authority @ CollectionError::Unauthorized,
+    )]
+    pub collection_info: Box<Account<'info, collection::CollectionInfo>>,
+}
+
+#[event]
+pub struct SetGameAuthorityEvent {
+    pub collection_id: u64,
+    pub game_authority: Pubkey,
+}
+
+pub fn set_dutch_auction(ctx: Context<SetDutchAuction>, collection_id: u64, dutch_auction: Option<collection::DutchAuction>) -> Result<()> {
+    // the rebate accounting depends on the schedule, so a rebate drop is locked until its clearing price
+    // is frozen, the proceeds withdrawn and every rebate paid out. the settled drop is then reset
+    let token_count = &mut ctx.accounts.token_count;
+    if token_count.dutch_minted > 0 {
+        require!(
+            token_count.dutch_clearing_price > 0
+                && token_count.dutch_withdrawn + token_count.dutch_rebated == token_count.dutch_escrowed,
+            CollectionError::DropNotSettled
+        );
+
+        token_count.dutch_minted = 0;
+        token_count.dutch_paid = 0;
+        token_count.dutch_last_price = 0;
+        token_count.dutch_escrowed = 0;
+        token_count.dutch_withdrawn = 0;
+        token_count.dutch_rebated = 0;
+        token_count.dutch_clearing_price = 0;
+        token_count.dutch_drop += 1;
+    }
+
+    if let Some(dutch) = dutch_auction {
+        // rebates are escrowed in lamports only
+        require!(
+            !dutch.rebate || ctx.accounts.collection_info.payment_mint.is_none(),
+            CollectionError::InvalidDutchAuction
+        );
+        require!(dutch.start_price >= dutch.floor_price, CollectionError::InvalidDutchAuction);
+        // a zero clearing price would read as not frozen and lock the drop
+        require!(!dutch.rebate || dutch.floor_price > 0, CollectionError::InvalidDutchAuction);
+        require!(dutch.decay_interval > 0 && dutch.step > 0, CollectionError::InvalidDutchAuction);
+    }
+
+    ctx.accounts.collection_info.dutch_auction = dutch_auction;
+
+    emit!(SetDutchAuctionEvent {
+        collection_id,
+        dutch_auction,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(collection_id: u64)]
+pub struct SetDutchAuction<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [COLLECTION_INFO, collection_id.to_le_bytes().as_ref()],
+        bump,
+        has_one = authority @ CollectionError::Unauthorized,
+    )]
+    pub collection_info: Box<Account<'info, collection::CollectionInfo>>,
+
+    #[account(
+        mut,
+        seeds = [TOKEN_COUNT_SEED, collection_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub token_count: Box<Account<'info, token_count::TokenCount>>,
+}
+
+#[event]
+pub struct SetDutchAuctionEvent {
+    pub collection_id: u64,
+    pub dutch_auction: Option<collection::DutchAuction>,
+}
+
+pub fn set_payment_mint(ctx: Context<SetPaymentMint>, collection_id: u64, payment_mint: Option<Pubkey>) -> Result<()> {
+    // prices are stored in the unit of the payment mint, so switching units requires clearing them first
+    let collection_info = &mut ctx.accounts.collection_info;
+    if payment_mint != collection_info.payment_mint {
+        require!(
+            collection_info.mint_price == 0
+                && collection_info.phases.iter().all(|phase| phase.price == 0)
+                && collection_info.dutch_auction.is_none(),
+            MarketPlaceError::InvalidPaymentMint
+        );
+    }
+
+    collection_info.payment_mint = payment_mint;
+
+    emit!(SetPaymentMintEvent {
+        collection_id,
+        payment_mint,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(collection_id: u64)]
+pub struct SetPaymentMint<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [COLLECTION_INFO, collection_id.to_le_bytes().as_ref()],
+        bump,
+        has_one = authority @ CollectionError::Unauthorized,
+    )]
+    pub collection_info: Box<Account<'info, collection::CollectionInfo>>,
+}
+
+#[event]
+pub struct SetPaymentMintEvent {
+    pub collection_id: u64,
+    pub payment_mint: Option<Pubkey>,
+}
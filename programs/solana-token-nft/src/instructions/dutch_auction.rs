@@ -0,0 +1,312 @@
+use anchor_lang::prelude::*;
+use crate::states::{collection, init, token_count, wallet_mint};
+use crate::constants::{COLLECTION_INFO, INIT_SEED, TOKEN_COUNT_SEED, WALLET_MINT_SEED};
+use crate::errors::CollectionError;
+
+pub(crate) fn dutch_price(dutch: &collection::DutchAuction, now: i64) -> Result<u64> {
+    require!(now >= dutch.start_time, CollectionError::MintPhaseNotActive);
+
+    let steps = ((now - dutch.start_time) / dutch.decay_interval) as u64;
+    Ok(dutch.start_price.saturating_sub(steps.saturating_mul(dutch.step)).max(dutch.floor_price))
+}
+
+// the drop ends when it sells out or the price reaches the floor. the clearing price is frozen
+// by the first claim or withdrawal so every rebate is computed against the same price
+fn clearing_price(collection_info: &collection::CollectionInfo, token_count: &mut token_count::TokenCount, now: i64) -> Result<u64> {
+    if token_count.dutch_clearing_price > 0 {
+        return Ok(token_count.dutch_clearing_price);
+    }
+
+    let dutch = collection_info.dutch_auction.filter(|dutch| dutch.rebate).ok_or(CollectionError::InvalidDutchAuction)?;
+    let sold_out = collection_info.max_supply > 0 && token_count.count >= collection_info.max_supply;
+    let clearing = if sold_out {
+        token_count.dutch_last_price
+    } else if dutch_price(&dutch, now)? == dutch.floor_price {
+        dutch.floor_price
+    } else {
+        return err!(CollectionError::DropNotEnded);
+    };
+
+    token_count.dutch_clearing_price = clearing;
+    Ok(clearing)
+}
+
+fn pay_from_escrow(collection_info: &AccountInfo, to: &AccountInfo, amount: u64) -> Result<()> {
+    **collection_info.try_borrow_mut_lamports()? -= amount;
+    **to.try_borrow_mut_lamports()? += amount;
+    Ok(())
+}
+
+// anyone can push a rebate to its wallet, so the authority can settle a drop before replacing it
+pub fn claim_dutch_rebate(ctx: Context<ClaimDutchRebate>, collection_id: u64) -> Result<()> {
+    let clearing = clearing_price(&ctx.accounts.collection_info, &mut ctx.accounts.token_count, Clock::get()?.unix_timestamp)?;
+
+    let wallet_mint = &mut ctx.accounts.wallet_mint;
+    require!(
+        !wallet_mint.rebate_claimed && wallet_mint.dutch_drop == ctx.accounts.token_count.dutch_drop,
+        CollectionError::RebateClaimed
+    );
+    wallet_mint.rebate_claimed = true;
+
+    let rebate = wallet_mint.dutch_minted.checked_mul(clearing)
+        .and_then(|cost| wallet_mint.dutch_paid.checked_sub(cost))
+        .ok_or(CollectionError::InvalidDutchAuction)?;
+    ctx.accounts.token_count.dutch_rebated += rebate;
+    if rebate > 0 {
+        msg!("paying dutch auction rebate");
+
+        pay_from_escrow(
+            &ctx.accounts.collection_info.to_account_info(),
+            &ctx.accounts.wallet.to_account_info(),
+            rebate,
+        )?;
+    }
+
+    emit!(ClaimDutchRebateEvent {
+        collection_id,
+        wallet: ctx.accounts.wallet.key(),
+        clearing_price: clearing,
+        rebate,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(collection_id: u64)]
+pub struct ClaimDutchRebate<'info> {
+    /// CHECK: address, the wallet_mint seeds tie it to the minting wallet
+    #[account(mut)]
+    pub wallet: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [COLLECTION_INFO, collection_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub collection_info: Box<Account<'info, collection::CollectionInfo>>,
+
+    #[account(
+        mut,
+        seeds = [TOKEN_COUNT_SEED, collection_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub token_count: Box<Account<'info, token_count::TokenCount>>,
+
+    #[account(
+        mut,
+        seeds = [WALLET_MINT_SEED, collection_id.to_le_bytes().as_ref(), wallet.key().as_ref()],
+        bump,
+    )]
+    pub wallet_mint: Box<Account<'info, wallet_mint::WalletMint>>,
+}
+
+#[event]
+pub struct ClaimDutchRebateEvent {
+    pub collection_id: u64,
+    pub wallet: Pubkey,
+    pub clearing_price: u64,
+    pub rebate: u64,
+}
+
+// everything escrowed above the rebates owed goes to the init vault, rebate mints stop once the clearing price is frozen
+pub fn withdraw_dutch_proceeds(ctx: Context<WithdrawDutchProceeds>, collection_id: u64) -> Result<()> {
+    let clearing = clearing_price(&ctx.accounts.collection_info, &mut ctx.accounts.token_count, Clock::get()?.unix_timestamp)?;
+
+    let token_count = &mut ctx.accounts.token_count;
+    let owed = token_count.dutch_minted.checked_mul(clearing)
+        .and_then(|cost| token_count.dutch_paid.checked_sub(cost))
+        .ok_or(CollectionError::InvalidDutchAuction)?;
+    let amount = token_count.dutch_escrowed
+        .checked_sub(token_count.dutch_withdrawn + owed)
+        .ok_or(CollectionError::InvalidDutchAuction)?;
+    token_count.dutch_withdrawn += amount;
+
+    if amount > 0 {
+        msg!("withdrawing dutch auction proceeds to vault");
+
+        pay_from_escrow(
+            &ctx.accounts.collection_info.to_account_info(),
+            &ctx.accounts.vault.to_account_info(),
+            amount,
+        )?;
+    }
+
+    emit!(WithdrawDutchProceedsEvent {
+        collection_id,
+        clearing_price: clearing,
+        amount,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(collection_id: u64)]
+pub struct WithdrawDutchProceeds<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [COLLECTION_INFO, collection_id.to_le_bytes().as_ref()],
+        bump,
+        has_one = authority @ CollectionError::Unauthorized,
+    )]
+    pub collection_info: Box<Account<'info, collection::CollectionInfo>>,
+
+    #[account(
+        mut,
+        seeds = [TOKEN_COUNT_SEED, collection_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub token_count: Box<Account<'info, token_count::TokenCount>>,
+
+    #[account(
+        seeds = [INIT_SEED],
+        bump,
+        has_one = vault,
+    )]
+    pub init: Box<Account<'info, init::Init>>,
+
+    /// CHECK: address
+    #[account(mut)]
+    pub vault: UncheckedAccount<'info>,
+}
+
+#[event]
+pub struct WithdrawDutchProceedsEvent {
+    pub collection_id: u64,
+    pub clearing_price: u64,
+    pub amount: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const START: i64 = 1_000;
+
+    fn dutch(rebate: bool) -> collection::DutchAuction {
+        collection::DutchAuction {
+            start_time: START,
+            start_price: 1_000,
+            floor_price: 400,
+            decay_interval: 60,
+            step: 100,
+            rebate,
+        }
+    }
+
+    fn collection_info(max_supply: u64, dutch_auction: Option<collection::DutchAuction>) -> collection::CollectionInfo {
+        collection::CollectionInfo {
+            id: 1,
+            mint: Pubkey::new_unique(),
+            name: String::from("collection"),
+            creator: Pubkey::new_unique(),
+            authority: Pubkey::new_unique(),
+            max_supply,
+            mint_price: 0,
+            voucher_signer: [0u8; 20],
+            allowlist_signer: Pubkey::default(),
+            merkle_root: [0u8; 32],
+            phases: Vec::new(),
+            phases_version: 0,
+            seller_fee_basis_points: 0,
+            creators: Vec::new(),
+            edition_max_supply: 0,
+            rule_set: None,
+            uses: None,
+            game_authority: Pubkey::default(),
+            reveal: None,
+            dutch_auction,
+            payment_mint: None,
+        }
+    }
+
+    fn token_count(count: u64, dutch_last_price: u64) -> token_count::TokenCount {
+        token_count::TokenCount {
+            count,
+            burned: 0,
+            dutch_minted: count,
+            dutch_paid: 0,
+            dutch_last_price,
+            dutch_escrowed: 0,
+            dutch_withdrawn: 0,
+            dutch_rebated: 0,
+            dutch_clearing_price: 0,
+            dutch_drop: 0,
+        }
+    }
+
+    #[test]
+    fn dutch_price_drops_a_step_every_interval() {
+        assert_eq!(dutch_price(&dutch(false), START).unwrap(), 1_000);
+        assert_eq!(dutch_price(&dutch(false), START + 59).unwrap(), 1_000);
+        assert_eq!(dutch_price(&dutch(false), START + 60).unwrap(), 900);
+        assert_eq!(dutch_price(&dutch(false), START + 150).unwrap(), 800);
+    }
+
+    #[test]
+    fn dutch_price_stops_at_the_floor() {
+        assert_eq!(dutch_price(&dutch(false), START + 360).unwrap(), 400);
+        assert_eq!(dutch_price(&dutch(false), i64::MAX).unwrap(), 400);
+    }
+
+    #[test]
+    fn dutch_price_rejects_mints_before_the_start() {
+        assert_eq!(dutch_price(&dutch(false), START - 1).err(), Some(CollectionError::MintPhaseNotActive.into()));
+    }
+
+    #[test]
+    fn clearing_price_is_the_last_price_when_sold_out() {
+        let collection_info = collection_info(10, Some(dutch(true)));
+        let mut token_count = token_count(10, 700);
+
+        assert_eq!(clearing_price(&collection_info, &mut token_count, START + 180).unwrap(), 700);
+        assert_eq!(token_count.dutch_clearing_price, 700);
+    }
+
+    #[test]
+    fn clearing_price_is_the_floor_once_reached() {
+        let collection_info = collection_info(10, Some(dutch(true)));
+        let mut token_count = token_count(4, 500);
+
+        assert_eq!(clearing_price(&collection_info, &mut token_count, START + 360).unwrap(), 400);
+    }
+
+    #[test]
+    fn clearing_price_stays_frozen() {
+        let collection_info = collection_info(10, Some(dutch(true)));
+        let mut token_count = token_count(10, 700);
+        clearing_price(&collection_info, &mut token_count, START + 180).unwrap();
+
+        token_count.dutch_last_price = 400;
+        assert_eq!(clearing_price(&collection_info, &mut token_count, START + 360).unwrap(), 700);
+    }
+
+    #[test]
+    fn clearing_price_waits_for_the_drop_to_end() {
+        let collection_info = collection_info(10, Some(dutch(true)));
+        let mut token_count = token_count(4, 800);
+
+        assert_eq!(
+            clearing_price(&collection_info, &mut token_count, START + 180).err(),
+            Some(CollectionError::DropNotEnded.into())
+        );
+        assert_eq!(token_count.dutch_clearing_price, 0);
+    }
+
+    #[test]
+    fn clearing_price_requires_a_rebate_drop() {
+        let mut token_count = token_count(10, 700);
+
+        assert_eq!(
+            clearing_price(&collection_info(10, Some(dutch(false))), &mut token_count, START + 360).err(),
+            Some(CollectionError::InvalidDutchAuction.into())
+        );
+        assert_eq!(
+            clearing_price(&collection_info(10, None), &mut token_count, START + 360).err(),
+            Some(CollectionError::InvalidDutchAuction.into())
+        );
+    }
+}
@@ -0,0 +1,813 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{program::invoke, system_instruction};
+use anchor_spl::token;
+use anchor_spl::associated_token::{self, get_associated_token_address_with_program_id, AssociatedToken};
+use anchor_spl::metadata::MetadataAccount;
+use anchor_spl::token::{CloseAccount, Mint, Token, TokenAccount, Transfer};
+use anchor_spl::token_interface::{self, TokenInterface, TransferChecked};
+use crate::states::{init, item};
+use crate::constants::{COLLECTION_SEED, INIT_SEED, ITEM_SEED, TOKEN_SEED, VAULT_SEED};
+use crate::errors::{CollectionError, MarketPlaceError};
+use crate::instructions::spl_token::find_metadata_account;
+
+// moves the nft out of an escrow vault and closes it, the rent goes to destination
+pub(crate) fn release_vault<'info>(
+    token_program: &Program<'info, Token>,
+    vault: &Account<'info, TokenAccount>,
+    to: AccountInfo<'info>,
+    authority: AccountInfo<'info>,
+    destination: AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    token::transfer(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            Transfer {
+                from: vault.to_account_info(),
+                to,
+                authority: authority.clone(),
+            },
+            signer_seeds,
+        ),
+        1,
+    )?;
+
+    token::close_account(CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        CloseAccount {
+            account: vault.to_account_info(),
+            destination,
+            authority,
+        },
+        signer_seeds,
+    ))
+}
+
+pub(crate) struct SaleSplit {
+    pub fee: u64,
+    pub royalties: u64,
+}
+
+// splits a sale between the platform vault, the metadata creators and the seller.
+// creators are passed in metadata order, the collection PDA creator only signs and is never paid.
+// rounding dust is left to the seller
+pub(crate) fn split_sale<'info>(
+    price: u64,
+    fee_basis_points: u16,
+    metadata: &MetadataAccount,
+    creators: &[AccountInfo<'info>],
+    vault: &AccountInfo<'info>,
+    seller: &AccountInfo<'info>,
+    mut pay: impl FnMut(&AccountInfo<'info>, u64) -> Result<()>,
+) -> Result<SaleSplit> {
+    require!(
+        fee_basis_points as u64 + metadata.seller_fee_basis_points as u64 <= 10000,
+        MarketPlaceError::InvalidFee
+    );
+
+    let fee = (price as u128 * fee_basis_points as u128 / 10000) as u64;
+    if fee > 0 {
+        pay(vault, fee)?;
+    }
+
+    let royalty_total = (price as u128 * metadata.seller_fee_basis_points as u128 / 10000) as u64;
+    let collection_key = metadata.collection.as_ref().map(|collection| collection.key);
+    let metadata_creators = metadata.creators.clone().unwrap_or_default();
+    require!(creators.len() == metadata_creators.len(), MarketPlaceError::InvalidCreator);
+
+    let mut royalties: u64 = 0;
+    for (creator, account) in metadata_creators.iter().zip(creators.iter()) {
+        require_keys_eq!(creator.address, account.key(), MarketPlaceError::InvalidCreator);
+        if creator.share == 0 || Some(creator.address) == collection_key {
+            continue;
+        }
+
+        let royalty = (royalty_total as u128 * creator.share as u128 / 100) as u64;
+        if royalty > 0 {
+            pay(account, royalty)?;
+            royalties += royalty;
+        }
+    }
+
+    let proceeds = price - fee - royalties;
+    if proceeds > 0 {
+        pay(seller, proceeds)?;
+    }
+
+    Ok(SaleSplit {
+        fee,
+        royalties,
+    })
+}
+
+// burns the configured share of a payment made with the program token, other mints are never burned
+pub(crate) fn burn_payment_share<'info>(
+    init: &init::Init,
+    token_program: &Interface<'info, TokenInterface>,
+    payment_mint: &InterfaceAccount<'info, token_interface::Mint>,
+    from: &InterfaceAccount<'info, token_interface::TokenAccount>,
+    authority: AccountInfo<'info>,
+    amount: u64,
+) -> Result<u64> {
+    let (token_mint, _) = Pubkey::find_program_address(&[TOKEN_SEED], &crate::ID);
+    if init.burn_basis_points == 0 || payment_mint.key() != token_mint {
+        return Ok(0);
+    }
+
+    let burned = (amount as u128 * init.burn_basis_points as u128 / 10000) as u64;
+    if burned > 0 {
+        token_interface::burn(
+            CpiContext::new(
+                token_program.to_account_info(),
+                token_interface::Burn {
+                    mint: payment_mint.to_account_info(),
+                    from: from.to_account_info(),
+                    authority,
+                },
+            ),
+            burned,
+        )?;
+    }
+
+    Ok(burned)
+}
+
+pub(crate) fn transfer_payment<'info>(
+    token_program: &Interface<'info, TokenInterface>,
+    payment_mint: &InterfaceAccount<'info, token_interface::Mint>,
+    from: &InterfaceAccount<'info, token_interface::TokenAccount>,
+    authority: AccountInfo<'info>,
+    to: AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    token_interface::transfer_checked(
+        CpiContext::new(
+            token_program.to_account_info(),
+            TransferChecked {
+                from: from.to_account_info(),
+                mint: payment_mint.to_account_info(),
+                to,
+                authority,
+            },
+        ),
+        amount,
+        payment_mint.decimals,
+    )
+}
+
+pub fn set_platform_fee(ctx: Context<SetPlatformFee>, fee_basis_points: u16) -> Result<()> {
+    require!(fee_basis_points <= 10000, MarketPlaceError::InvalidFee);
+
+    ctx.accounts.init.fee_basis_points = fee_basis_points;
+
+    emit!(SetPlatformFeeEvent {
+        fee_basis_points,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetPlatformFee<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [INIT_SEED],
+        bump,
+        has_one = admin @ CollectionError::Unauthorized,
+    )]
+    pub init: Box<Account<'info, init::Init>>,
+}
+
+#[event]
+pub struct SetPlatformFeeEvent {
+    pub fee_basis_points: u16,
+}
+
+pub fn set_payment_burn(ctx: Context<SetPlatformFee>, burn_basis_points: u16) -> Result<()> {
+    require!(burn_basis_points <= 10000, MarketPlaceError::InvalidFee);
+
+    ctx.accounts.init.burn_basis_points = burn_basis_points;
+
+    emit!(SetPaymentBurnEvent {
+        burn_basis_points,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct SetPaymentBurnEvent {
+    pub burn_basis_points: u16,
+}
+
+// only plain collection nfts can be listed, programmable nfts stay frozen in their token account
+pub fn list_item(ctx: Context<ListItem>, collection_id: u64, price: u64, payment_mint: Option<Pubkey>) -> Result<()> {
+    require!(price > 0, MarketPlaceError::InvalidPrice);
+
+    let item = &mut ctx.accounts.item;
+    require!(!item.listed, MarketPlaceError::ItemAlreadyListed);
+    item.collection_id = collection_id;
+    item.mint = ctx.accounts.mint.key();
+    item.seller = ctx.accounts.seller.key();
+    item.price = price;
+    item.payment_mint = payment_mint;
+    item.listed = true;
+
+    msg!("moving nft into the vault");
+
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.seller_token_account.to_account_info(),
+                to: ctx.accounts.vault.to_account_info(),
+                authority: ctx.accounts.seller.to_account_info(),
+            },
+        ),
+        1,
+    )?;
+
+    emit!(ListItemEvent {
+        collection_id,
+        mint: ctx.accounts.mint.key(),
+        seller: ctx.accounts.seller.key(),
+        price,
+        payment_mint,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(collection_id: u64)]
+pub struct ListItem<'info> {
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    #[account(
+        seeds = [COLLECTION_SEED, collection_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub collection_mint: Box<Account<'info, Mint>>,
+
+    pub mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        address = find_metadata_account(&mint.key()).0,
+        constraint = metadata_account.collection.as_ref().is_some_and(|c| c.verified && c.key == collection_mint.key()) @ CollectionError::InvalidCollection,
+    )]
+    pub metadata_account: Box<Account<'info, MetadataAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = seller,
+    )]
+    pub seller_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = seller,
+        space = 8 + item::Item::INIT_SPACE,
+        seeds = [ITEM_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub item: Box<Account<'info, item::Item>>,
+
+    #[account(
+        init_if_needed,
+        payer = seller,
+        seeds = [VAULT_SEED, mint.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = item,
+    )]
+    pub vault: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[event]
+pub struct ListItemEvent {
+    pub collection_id: u64,
+    pub mint: Pubkey,
+    pub seller: Pubkey,
+    pub price: u64,
+    pub payment_mint: Option<Pubkey>,
+}
+
+pub fn cancel_listing(ctx: Context<CancelListing>) -> Result<()> {
+    require!(ctx.accounts.item.listed, MarketPlaceError::ItemCanceledInvalid);
+
+    msg!("returning nft to the seller");
+
+    let mint = ctx.accounts.item.mint;
+    let signer_seeds: &[&[&[u8]]] = &[&[ITEM_SEED, mint.as_ref(), &[ctx.bumps.item]]];
+    release_vault(
+        &ctx.accounts.token_program,
+        &ctx.accounts.vault,
+        ctx.accounts.seller_token_account.to_account_info(),
+        ctx.accounts.item.to_account_info(),
+        ctx.accounts.seller.to_account_info(),
+        signer_seeds,
+    )?;
+
+    let item = &mut ctx.accounts.item;
+    item.listed = false;
+
+    emit!(CancelListingEvent {
+        collection_id: item.collection_id,
+        mint: item.mint,
+        seller: item.seller,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CancelListing<'info> {
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    pub mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [ITEM_SEED, mint.key().as_ref()],
+        bump,
+        has_one = seller @ CollectionError::Unauthorized,
+    )]
+    pub item: Box<Account<'info, item::Item>>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = seller,
+        associated_token::mint = mint,
+        associated_token::authority = seller,
+    )]
+    pub seller_token_account: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event]
+pub struct CancelListingEvent {
+    pub collection_id: u64,
+    pub mint: Pubkey,
+    pub seller: Pubkey,
+}
+
+// remaining_accounts holds the metadata creators in order. the buyer passes the price it saw so a relist
+// at a higher price in the meantime fails instead of charging more
+pub fn buy_item<'info>(ctx: Context<'_, '_, 'info, 'info, BuyItem<'info>>, price: u64) -> Result<()> {
+    require!(ctx.accounts.item.listed, MarketPlaceError::ItemCanceledInvalid);
+    require!(price == ctx.accounts.item.price, MarketPlaceError::InvalidPrice);
+    require!(ctx.accounts.item.payment_mint.is_none(), MarketPlaceError::InvalidPaymentMint);
+    require!(ctx.accounts.buyer.lamports() >= price, MarketPlaceError::InsufficientBalance);
+
+    msg!("paying platform fee, royalties and seller");
+
+    let buyer = ctx.accounts.buyer.to_account_info();
+    let system_program = ctx.accounts.system_program.to_account_info();
+    let split = split_sale(
+        price,
+        ctx.accounts.init.fee_basis_points,
+        &ctx.accounts.metadata_account,
+        ctx.remaining_accounts,
+        &ctx.accounts.fee_vault.to_account_info(),
+        &ctx.accounts.seller.to_account_info(),
+        |to, amount| {
+            invoke(
+                &system_instruction::transfer(buyer.key, to.key, amount),
+                &[buyer.clone(), to.clone(), system_program.clone()],
+            )
+            .map_err(Into::into)
+        },
+    )?;
+
+    msg!("moving nft to the buyer");
+
+    let mint = ctx.accounts.item.mint;
+    let signer_seeds: &[&[&[u8]]] = &[&[ITEM_SEED, mint.as_ref(), &[ctx.bumps.item]]];
+    release_vault(
+        &ctx.accounts.token_program,
+        &ctx.accounts.vault,
+        ctx.accounts.buyer_token_account.to_account_info(),
+        ctx.accounts.item.to_account_info(),
+        ctx.accounts.seller.to_account_info(),
+        signer_seeds,
+    )?;
+
+    let item = &mut ctx.accounts.item;
+    item.listed = false;
+
+    emit!(BuyItemEvent {
+        collection_id: item.collection_id,
+        mint: item.mint,
+        seller: item.seller,
+        buyer: ctx.accounts.buyer.key(),
+        price,
+        payment_mint: None,
+        fee: split.fee,
+        royalties: split.royalties,
+        burned: 0,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct BuyItem<'info> {
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    /// CHECK: address
+    #[account(mut)]
+    pub seller: UncheckedAccount<'info>,
+
+    pub mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [ITEM_SEED, mint.key().as_ref()],
+        bump,
+        has_one = seller,
+    )]
+    pub item: Box<Account<'info, item::Item>>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        associated_token::mint = mint,
+        associated_token::authority = buyer,
+    )]
+    pub buyer_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        address = find_metadata_account(&mint.key()).0,
+    )]
+    pub metadata_account: Box<Account<'info, MetadataAccount>>,
+
+    #[account(
+        seeds = [INIT_SEED],
+        bump,
+    )]
+    pub init: Box<Account<'info, init::Init>>,
+
+    /// CHECK: address
+    #[account(
+        mut,
+        address = init.vault,
+    )]
+    pub fee_vault: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event]
+pub struct BuyItemEvent {
+    pub collection_id: u64,
+    pub mint: Pubkey,
+    pub seller: Pubkey,
+    pub buyer: Pubkey,
+    pub price: u64,
+    pub payment_mint: Option<Pubkey>,
+    pub fee: u64,
+    pub royalties: u64,
+    pub burned: u64,
+}
+
+// remaining_accounts holds the metadata creators in order, followed by the payment ATAs of the creators
+// that get paid, missing ATAs are created at the buyer's expense. the burned share comes off the price
+// before fees and royalties
+pub fn buy_item_with_token<'info>(ctx: Context<'_, '_, 'info, 'info, BuyItemWithToken<'info>>, price: u64) -> Result<()> {
+    require!(ctx.accounts.item.listed, MarketPlaceError::ItemCanceledInvalid);
+    require!(price == ctx.accounts.item.price, MarketPlaceError::InvalidPrice);
+    require!(ctx.accounts.buyer_payment_account.amount >= price, MarketPlaceError::InsufficientBalance);
+
+    let burned = burn_payment_share(
+        &ctx.accounts.init,
+        &ctx.accounts.payment_token_program,
+        &ctx.accounts.payment_mint,
+        &ctx.accounts.buyer_payment_account,
+        ctx.accounts.buyer.to_account_info(),
+        price,
+    )?;
+
+    msg!("paying platform fee, royalties and seller in tokens");
+
+    let creator_count = ctx.accounts.metadata_account.creators.as_ref().map_or(0, |creators| creators.len());
+    require!(ctx.remaining_accounts.len() >= creator_count, MarketPlaceError::InvalidCreator);
+    let (creators, creator_payment_accounts) = ctx.remaining_accounts.split_at(creator_count);
+
+    let accounts = &ctx.accounts;
+    let payment_mint_key = accounts.payment_mint.key();
+    let payment_program_key = accounts.payment_token_program.key();
+    let split = split_sale(
+        price - burned,
+        accounts.init.fee_basis_points,
+        &accounts.metadata_account,
+        creators,
+        &accounts.fee_vault.to_account_info(),
+        &accounts.seller.to_account_info(),
+        |to, amount| {
+            let destination = if to.key() == accounts.fee_vault.key() {
+                accounts.fee_vault_payment_account.to_account_info()
+            } else if to.key() == accounts.seller.key() {
+                accounts.seller_payment_account.to_account_info()
+            } else {
+                let creator_payment_account = get_associated_token_address_with_program_id(to.key, &payment_mint_key, &payment_program_key);
+                let creator_payment_account = creator_payment_accounts.iter()
+                    .find(|account| account.key() == creator_payment_account)
+                    .ok_or(MarketPlaceError::InvalidCreator)?
+                    .clone();
+
+                if creator_payment_account.data_is_empty() {
+                    associated_token::create_idempotent(CpiContext::new(
+                        accounts.associated_token_program.to_account_info(),
+                        associated_token::Create {
+                            payer: accounts.buyer.to_account_info(),
+                            associated_token: creator_payment_account.clone(),
+                            authority: to.clone(),
+                            mint: accounts.payment_mint.to_account_info(),
+                            system_program: accounts.system_program.to_account_info(),
+                            token_program: accounts.payment_token_program.to_account_info(),
+                        },
+                    ))?;
+                }
+
+                creator_payment_account
+            };
+
+            transfer_payment(
+                &accounts.payment_token_program,
+                &accounts.payment_mint,
+                &accounts.buyer_payment_account,
+                accounts.buyer.to_account_info(),
+                destination,
+                amount,
+            )
+        },
+    )?;
+
+    msg!("moving nft to the buyer");
+
+    let mint = ctx.accounts.item.mint;
+    let signer_seeds: &[&[&[u8]]] = &[&[ITEM_SEED, mint.as_ref(), &[ctx.bumps.item]]];
+    release_vault(
+        &ctx.accounts.token_program,
+        &ctx.accounts.vault,
+        ctx.accounts.buyer_token_account.to_account_info(),
+        ctx.accounts.item.to_account_info(),
+        ctx.accounts.seller.to_account_info(),
+        signer_seeds,
+    )?;
+
+    let item = &mut ctx.accounts.item;
+    item.listed = false;
+
+    emit!(BuyItemEvent {
+        collection_id: item.collection_id,
+        mint: item.mint,
+        seller: item.seller,
+        buyer: ctx.accounts.buyer.key(),
+        price,
+        payment_mint: item.payment_mint,
+        fee: split.fee,
+        royalties: split.royalties,
+        burned,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct BuyItemWithToken<'info> {
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    /// CHECK: address
+    #[account(mut)]
+    pub seller: UncheckedAccount<'info>,
+
+    pub mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [ITEM_SEED, mint.key().as_ref()],
+        bump,
+        has_one = seller,
+        constraint = item.payment_mint == Some(payment_mint.key()) @ MarketPlaceError::InvalidPaymentMint,
+    )]
+    pub item: Box<Account<'info, item::Item>>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        associated_token::mint = mint,
+        associated_token::authority = buyer,
+    )]
+    pub buyer_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        address = find_metadata_account(&mint.key()).0,
+    )]
+    pub metadata_account: Box<Account<'info, MetadataAccount>>,
+
+    #[account(
+        seeds = [INIT_SEED],
+        bump,
+    )]
+    pub init: Box<Account<'info, init::Init>>,
+
+    /// CHECK: address
+    #[account(
+        address = init.vault,
+    )]
+    pub fee_vault: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payment_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = payment_mint,
+        associated_token::authority = buyer,
+        associated_token::token_program = payment_token_program,
+    )]
+    pub buyer_payment_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        associated_token::mint = payment_mint,
+        associated_token::authority = seller,
+        associated_token::token_program = payment_token_program,
+    )]
+    pub seller_payment_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        associated_token::mint = payment_mint,
+        associated_token::authority = fee_vault,
+        associated_token::token_program = payment_token_program,
+    )]
+    pub fee_vault_payment_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    pub payment_token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mpl_token_metadata::accounts::Metadata;
+    use mpl_token_metadata::types::{Collection, Creator, Key};
+
+    fn creator(address: Pubkey, share: u8) -> Creator {
+        Creator {
+            address,
+            verified: true,
+            share,
+        }
+    }
+
+    fn metadata_account(seller_fee_basis_points: u16, collection_mint: Pubkey, creators: Vec<Creator>) -> MetadataAccount {
+        let metadata = Metadata {
+            key: Key::MetadataV1,
+            update_authority: collection_mint,
+            mint: Pubkey::new_unique(),
+            name: String::from("item"),
+            symbol: String::from("ITEM"),
+            uri: String::new(),
+            seller_fee_basis_points,
+            creators: Some(creators),
+            primary_sale_happened: false,
+            is_mutable: true,
+            edition_nonce: None,
+            token_standard: None,
+            collection: Some(Collection {
+                verified: true,
+                key: collection_mint,
+            }),
+            uses: None,
+            collection_details: None,
+            programmable_config: None,
+        };
+        let data = metadata.try_to_vec().unwrap();
+        MetadataAccount::try_deserialize(&mut data.as_slice()).unwrap()
+    }
+
+    // runs split_sale against plain accounts and records every payment in order
+    fn run_split_sale(price: u64, fee_basis_points: u16, metadata: &MetadataAccount, vault: Pubkey, seller: Pubkey, creators: &[Pubkey]) -> Result<(SaleSplit, Vec<(Pubkey, u64)>)> {
+        let owner = Pubkey::default();
+        let keys: Vec<Pubkey> = [vault, seller].iter().chain(creators).copied().collect();
+        let mut lamports = vec![0u64; keys.len()];
+        let mut data = vec![Vec::<u8>::new(); keys.len()];
+        let accounts: Vec<AccountInfo> = keys.iter()
+            .zip(lamports.iter_mut())
+            .zip(data.iter_mut())
+            .map(|((key, lamports), data)| AccountInfo::new(key, false, true, lamports, data, &owner, false, 0))
+            .collect();
+
+        let mut payments = Vec::new();
+        let split = split_sale(price, fee_basis_points, metadata, &accounts[2..], &accounts[0], &accounts[1], |to, amount| {
+            payments.push((*to.key, amount));
+            Ok(())
+        })?;
+
+        Ok((split, payments))
+    }
+
+    #[test]
+    fn split_sale_pays_fee_royalties_and_seller() {
+        let (collection_mint, vault, seller) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let (artist, studio) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let metadata = metadata_account(500, collection_mint, vec![creator(collection_mint, 0), creator(artist, 60), creator(studio, 40)]);
+
+        let (split, payments) = run_split_sale(10_000, 250, &metadata, vault, seller, &[collection_mint, artist, studio]).unwrap();
+
+        assert_eq!(split.fee, 250);
+        assert_eq!(split.royalties, 500);
+        assert_eq!(payments, vec![(vault, 250), (artist, 300), (studio, 200), (seller, 9_250)]);
+    }
+
+    #[test]
+    fn split_sale_leaves_rounding_dust_to_seller() {
+        let (collection_mint, vault, seller) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let (artist, studio) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let metadata = metadata_account(300, collection_mint, vec![creator(artist, 50), creator(studio, 50)]);
+
+        let (split, payments) = run_split_sale(999, 100, &metadata, vault, seller, &[artist, studio]).unwrap();
+
+        assert_eq!(split.fee, 9);
+        assert_eq!(split.royalties, 28);
+        assert_eq!(payments, vec![(vault, 9), (artist, 14), (studio, 14), (seller, 962)]);
+    }
+
+    #[test]
+    fn split_sale_skips_zero_amounts() {
+        let (collection_mint, vault, seller) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let metadata = metadata_account(0, collection_mint, vec![creator(collection_mint, 100)]);
+
+        let (split, payments) = run_split_sale(1_000, 0, &metadata, vault, seller, &[collection_mint]).unwrap();
+
+        assert_eq!(split.fee, 0);
+        assert_eq!(split.royalties, 0);
+        assert_eq!(payments, vec![(seller, 1_000)]);
+    }
+
+    #[test]
+    fn split_sale_rejects_creators_out_of_metadata_order() {
+        let (collection_mint, vault, seller) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let (artist, studio) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let metadata = metadata_account(500, collection_mint, vec![creator(artist, 60), creator(studio, 40)]);
+
+        let result = run_split_sale(10_000, 250, &metadata, vault, seller, &[studio, artist]);
+        assert_eq!(result.err(), Some(MarketPlaceError::InvalidCreator.into()));
+
+        let result = run_split_sale(10_000, 250, &metadata, vault, seller, &[artist]);
+        assert_eq!(result.err(), Some(MarketPlaceError::InvalidCreator.into()));
+    }
+
+    #[test]
+    fn split_sale_rejects_fee_and_royalties_above_price() {
+        let (collection_mint, vault, seller) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let metadata = metadata_account(5_000, collection_mint, vec![creator(collection_mint, 100)]);
+
+        let result = run_split_sale(10_000, 5_001, &metadata, vault, seller, &[collection_mint]);
+        assert_eq!(result.err(), Some(MarketPlaceError::InvalidFee.into()));
+    }
+}
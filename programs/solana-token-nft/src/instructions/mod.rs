@@ -3,3 +3,30 @@ pub use spl_token::*;
 
 pub mod signature;
 pub use signature::*;
+
+pub mod collection_config;
+pub use collection_config::*;
+
+pub mod nft;
+pub use nft::*;
+
+pub mod pnft;
+pub use pnft::*;
+
+pub mod batch_mint;
+pub use batch_mint::*;
+
+pub mod traits;
+pub use traits::*;
+
+pub mod marketplace;
+pub use marketplace::*;
+
+pub mod offer;
+pub use offer::*;
+
+pub mod auction;
+pub use auction::*;
+
+pub mod dutch_auction;
+pub use dutch_auction::*;
@@ -0,0 +1,705 @@
+use anchor_lang::prelude::*;
+use anchor_spl::metadata;
+use anchor_spl::token;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::metadata::{BurnNft, FreezeDelegatedAccount, MasterEditionAccount, Metadata, MetadataAccount, MintNewEditionFromMasterEditionViaToken, ThawDelegatedAccount, UpdateMetadataAccountsV2, Utilize};
+use anchor_spl::token::{Mint, MintTo, Token, TokenAccount};
+use mpl_token_metadata::instructions::UpdateV1CpiBuilder;
+use mpl_token_metadata::types::{Data, DataV2, UseMethod};
+use solana_program::hash;
+use solana_program::sysvar::instructions::ID as IX_ID;
+use crate::states::{collection, token_count};
+use crate::constants::{COLLECTION_INFO, COLLECTION_SEED, EDITION_SEED, METADATA_SEED, TOKEN_COUNT_SEED};
+use crate::errors::CollectionError;
+use crate::utils;
+use crate::instructions::spl_token::{find_master_edition_account, find_metadata_account};
+
+fn find_edition_marker_account(master_mint: &Pubkey, edition: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            METADATA_SEED,
+            mpl_token_metadata::ID.as_ref(),
+            master_mint.as_ref(),
+            EDITION_SEED,
+            (edition / 248).to_string().as_bytes(),
+        ], &mpl_token_metadata::ID)
+}
+
+pub fn update_nft_metadata(ctx: Context<UpdateNftMetadata>, collection_id: u64, name: Option<String>, symbol: Option<String>, uri: Option<String>, seller_fee_basis_points: Option<u16>, is_mutable: Option<bool>) -> Result<()> {
+    let collection_id_bytes = collection_id.to_le_bytes();
+    let signer_seeds: &[&[&[u8]]] = &[&[COLLECTION_SEED, &collection_id_bytes, &[ctx.bumps.collection_mint]]];
+
+    if let Some(bps) = seller_fee_basis_points {
+        require!(bps <= 10000, CollectionError::InvalidRoyalties);
+    }
+
+    // metaplex stores name, symbol and uri padded with null bytes
+    let current = &ctx.accounts.metadata_account;
+    let data_v2 = DataV2 {
+        name: name.unwrap_or_else(|| current.name.trim_end_matches('\0').to_string()),
+        symbol: symbol.unwrap_or_else(|| current.symbol.trim_end_matches('\0').to_string()),
+        uri: uri.unwrap_or_else(|| current.uri.trim_end_matches('\0').to_string()),
+        seller_fee_basis_points: seller_fee_basis_points.unwrap_or(current.seller_fee_basis_points),
+        creators: current.creators.clone(),
+        collection: current.collection.clone(),
+        uses: current.uses.clone(),
+    };
+
+    msg!("updating nft metadata");
+
+    metadata::update_metadata_accounts_v2(
+        CpiContext::new_with_signer(
+            ctx.accounts.metadata_program.to_account_info(),
+            UpdateMetadataAccountsV2 {
+                metadata: ctx.accounts.metadata_account.to_account_info(),
+                update_authority: ctx.accounts.collection_mint.to_account_info(),
+            },
+            signer_seeds
+        ),
+        None,
+        Some(data_v2.clone()),
+        None,
+        is_mutable,
+    )?;
+
+    emit!(UpdateNftMetadataEvent {
+        collection_id,
+        mint: ctx.accounts.mint.key(),
+        name: data_v2.name,
+        symbol: data_v2.symbol,
+        uri: data_v2.uri,
+        seller_fee_basis_points: data_v2.seller_fee_basis_points,
+        is_mutable: is_mutable.unwrap_or(current.is_mutable),
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(collection_id: u64)]
+pub struct UpdateNftMetadata<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [COLLECTION_INFO, collection_id.to_le_bytes().as_ref()],
+        bump,
+        has_one = authority @ CollectionError::Unauthorized,
+    )]
+    pub collection_info: Box<Account<'info, collection::CollectionInfo>>,
+
+    #[account(
+        seeds = [COLLECTION_SEED, collection_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub collection_mint: Box<Account<'info, Mint>>,
+
+    pub mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        address = find_metadata_account(&mint.key()).0
+    )]
+    pub metadata_account: Box<Account<'info, MetadataAccount>>,
+
+    pub metadata_program: Program<'info, Metadata>,
+}
+
+#[event]
+pub struct UpdateNftMetadataEvent {
+    pub collection_id: u64,
+    pub mint: Pubkey,
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub seller_fee_basis_points: u16,
+    pub is_mutable: bool,
+}
+
+pub fn burn_nft(ctx: Context<BurnNftItem>, collection_id: u64) -> Result<()> {
+    msg!("burning nft");
+
+    // passing the collection metadata lets metaplex decrement the sized collection
+    metadata::burn_nft(
+        CpiContext::new(
+            ctx.accounts.metadata_program.to_account_info(),
+            BurnNft {
+                metadata: ctx.accounts.metadata_account.to_account_info(),
+                owner: ctx.accounts.owner.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                token: ctx.accounts.token_account.to_account_info(),
+                edition: ctx.accounts.master_edition.to_account_info(),
+                spl_token: ctx.accounts.token_program.to_account_info(),
+            },
+        ).with_remaining_accounts(vec![ctx.accounts.collection_metadata_account.to_account_info()]),
+        Some(ctx.accounts.collection_metadata_account.key()),
+    )?;
+
+    ctx.accounts.token_count.burned += 1;
+
+    emit!(BurnNftEvent {
+        collection_id,
+        mint: ctx.accounts.mint.key(),
+        owner: ctx.accounts.owner.key(),
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(collection_id: u64)]
+pub struct BurnNftItem<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        seeds = [COLLECTION_SEED, collection_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub collection_mint: Box<Account<'info, Mint>>,
+
+    /// CHECK: address
+    #[account(
+        mut,
+        address = find_metadata_account(&collection_mint.key()).0
+    )]
+    pub collection_metadata_account: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [TOKEN_COUNT_SEED, collection_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub token_count: Box<Account<'info, token_count::TokenCount>>,
+
+    #[account(mut)]
+    pub mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = owner,
+    )]
+    pub token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        address = find_metadata_account(&mint.key()).0,
+        constraint = metadata_account.collection.as_ref().is_some_and(|c| c.verified && c.key == collection_mint.key()) @ CollectionError::InvalidCollection,
+    )]
+    pub metadata_account: Box<Account<'info, MetadataAccount>>,
+
+    /// CHECK: address
+    #[account(
+        mut,
+        address = find_master_edition_account(&mint.key()).0
+    )]
+    pub master_edition: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub metadata_program: Program<'info, Metadata>,
+}
+
+#[event]
+pub struct BurnNftEvent {
+    pub collection_id: u64,
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+}
+
+// the holder signs as metaplex use authority, the game server co-signs to gate every use
+pub fn utilize_nft(ctx: Context<UtilizeNft>, collection_id: u64) -> Result<()> {
+    let uses = ctx.accounts.metadata_account.uses.clone().ok_or(CollectionError::InvalidUses)?;
+    require!(uses.remaining > 0, CollectionError::NoUsesLeft);
+
+    let remaining = uses.remaining - 1;
+    let burned = remaining == 0 && uses.use_method == UseMethod::Burn;
+
+    if burned {
+        msg!("spending last use and burning nft");
+
+        // burning through burn_nft instead of letting utilize burn the token closes the accounts and shrinks the sized collection
+        metadata::burn_nft(
+            CpiContext::new(
+                ctx.accounts.metadata_program.to_account_info(),
+                BurnNft {
+                    metadata: ctx.accounts.metadata_account.to_account_info(),
+                    owner: ctx.accounts.owner.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    token: ctx.accounts.token_account.to_account_info(),
+                    edition: ctx.accounts.master_edition.to_account_info(),
+                    spl_token: ctx.accounts.token_program.to_account_info(),
+                },
+            ).with_remaining_accounts(vec![ctx.accounts.collection_metadata_account.to_account_info()]),
+            Some(ctx.accounts.collection_metadata_account.key()),
+        )?;
+
+        ctx.accounts.token_count.burned += 1;
+    } else {
+        msg!("spending nft use");
+
+        metadata::utilize(
+            CpiContext::new(
+                ctx.accounts.metadata_program.to_account_info(),
+                Utilize {
+                    metadata: ctx.accounts.metadata_account.to_account_info(),
+                    token_account: ctx.accounts.token_account.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    use_authority: ctx.accounts.owner.to_account_info(),
+                    owner: ctx.accounts.owner.to_account_info(),
+                },
+            ).with_remaining_accounts(vec![
+                ctx.accounts.token_program.to_account_info(),
+                ctx.accounts.associated_token_program.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+                ctx.accounts.rent.to_account_info(),
+            ]),
+            None,
+            None,
+            1,
+        )?;
+    }
+
+    emit!(UtilizeNftEvent {
+        collection_id,
+        mint: ctx.accounts.mint.key(),
+        owner: ctx.accounts.owner.key(),
+        remaining,
+        burned,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(collection_id: u64)]
+pub struct UtilizeNft<'info> {
+    pub game_authority: Signer<'info>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        seeds = [COLLECTION_INFO, collection_id.to_le_bytes().as_ref()],
+        bump,
+        has_one = game_authority @ CollectionError::Unauthorized,
+    )]
+    pub collection_info: Box<Account<'info, collection::CollectionInfo>>,
+
+    #[account(
+        seeds = [COLLECTION_SEED, collection_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub collection_mint: Box<Account<'info, Mint>>,
+
+    /// CHECK: address
+    #[account(
+        mut,
+        address = find_metadata_account(&collection_mint.key()).0
+    )]
+    pub collection_metadata_account: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [TOKEN_COUNT_SEED, collection_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub token_count: Box<Account<'info, token_count::TokenCount>>,
+
+    #[account(mut)]
+    pub mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = owner,
+    )]
+    pub token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        address = find_metadata_account(&mint.key()).0,
+        constraint = metadata_account.collection.as_ref().is_some_and(|c| c.verified && c.key == collection_mint.key()) @ CollectionError::InvalidCollection,
+    )]
+    pub metadata_account: Box<Account<'info, MetadataAccount>>,
+
+    /// CHECK: address
+    #[account(
+        mut,
+        address = find_master_edition_account(&mint.key()).0
+    )]
+    pub master_edition: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub metadata_program: Program<'info, Metadata>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[event]
+pub struct UtilizeNftEvent {
+    pub collection_id: u64,
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub remaining: u64,
+    pub burned: bool,
+}
+
+// the holder must first approve the collection mint PDA as delegate of its token account
+pub fn freeze_nft(ctx: Context<FreezeNft>, collection_id: u64) -> Result<()> {
+    let collection_id_bytes = collection_id.to_le_bytes();
+    let signer_seeds: &[&[&[u8]]] = &[&[COLLECTION_SEED, &collection_id_bytes, &[ctx.bumps.collection_mint]]];
+
+    msg!("freezing nft");
+
+    metadata::freeze_delegated_account(CpiContext::new_with_signer(
+        ctx.accounts.metadata_program.to_account_info(),
+        FreezeDelegatedAccount {
+            metadata: ctx.accounts.metadata_account.to_account_info(),
+            delegate: ctx.accounts.collection_mint.to_account_info(),
+            token_account: ctx.accounts.token_account.to_account_info(),
+            edition: ctx.accounts.master_edition.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            token_program: ctx.accounts.token_program.to_account_info(),
+        }, signer_seeds
+    ))?;
+
+    emit!(FreezeNftEvent {
+        collection_id,
+        mint: ctx.accounts.mint.key(),
+        token_account: ctx.accounts.token_account.key(),
+        frozen: true,
+    });
+
+    Ok(())
+}
+
+pub fn thaw_nft(ctx: Context<FreezeNft>, collection_id: u64) -> Result<()> {
+    let collection_id_bytes = collection_id.to_le_bytes();
+    let signer_seeds: &[&[&[u8]]] = &[&[COLLECTION_SEED, &collection_id_bytes, &[ctx.bumps.collection_mint]]];
+
+    msg!("thawing nft");
+
+    metadata::thaw_delegated_account(CpiContext::new_with_signer(
+        ctx.accounts.metadata_program.to_account_info(),
+        ThawDelegatedAccount {
+            metadata: ctx.accounts.metadata_account.to_account_info(),
+            delegate: ctx.accounts.collection_mint.to_account_info(),
+            token_account: ctx.accounts.token_account.to_account_info(),
+            edition: ctx.accounts.master_edition.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            token_program: ctx.accounts.token_program.to_account_info(),
+        }, signer_seeds
+    ))?;
+
+    emit!(FreezeNftEvent {
+        collection_id,
+        mint: ctx.accounts.mint.key(),
+        token_account: ctx.accounts.token_account.key(),
+        frozen: false,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(collection_id: u64)]
+pub struct FreezeNft<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [COLLECTION_INFO, collection_id.to_le_bytes().as_ref()],
+        bump,
+        has_one = authority @ CollectionError::Unauthorized,
+    )]
+    pub collection_info: Box<Account<'info, collection::CollectionInfo>>,
+
+    #[account(
+        seeds = [COLLECTION_SEED, collection_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub collection_mint: Box<Account<'info, Mint>>,
+
+    pub mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        constraint = token_account.delegate == Some(collection_mint.key()).into() @ CollectionError::NotDelegated,
+    )]
+    pub token_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: address
+    #[account(
+        address = find_metadata_account(&mint.key()).0
+    )]
+    pub metadata_account: UncheckedAccount<'info>,
+
+    /// CHECK: address
+    #[account(
+        address = find_master_edition_account(&mint.key()).0
+    )]
+    pub master_edition: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub metadata_program: Program<'info, Metadata>,
+}
+
+#[event]
+pub struct FreezeNftEvent {
+    pub collection_id: u64,
+    pub mint: Pubkey,
+    pub token_account: Pubkey,
+    pub frozen: bool,
+}
+
+// prints the next numbered edition of a master held by master_holder into the payer's wallet
+pub fn print_edition(ctx: Context<PrintEdition>, collection_id: u64) -> Result<()> {
+    let collection_id_bytes = collection_id.to_le_bytes();
+    let signer_seeds: &[&[&[u8]]] = &[&[COLLECTION_SEED, &collection_id_bytes, &[ctx.bumps.collection_mint]]];
+
+    let master_edition = &ctx.accounts.master_edition;
+    let edition = master_edition.supply + 1;
+    require!(
+        edition <= master_edition.max_supply.unwrap_or(u64::MAX),
+        CollectionError::EditionSupplyReached
+    );
+    require_keys_eq!(
+        ctx.accounts.edition_marker.key(),
+        find_edition_marker_account(&ctx.accounts.master_mint.key(), edition).0,
+        CollectionError::InvalidEditionMarker
+    );
+
+    msg!("minting print edition {}", edition);
+
+    token::mint_to(CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        MintTo {
+            mint: ctx.accounts.new_mint.to_account_info(),
+            to: ctx.accounts.new_token_account.to_account_info(),
+            authority: ctx.accounts.collection_mint.to_account_info(),
+        }, signer_seeds), 1)?;
+
+    metadata::mint_new_edition_from_master_edition_via_token(
+        CpiContext::new_with_signer(
+            ctx.accounts.metadata_program.to_account_info(),
+            MintNewEditionFromMasterEditionViaToken {
+                new_metadata: ctx.accounts.new_metadata_account.to_account_info(),
+                new_edition: ctx.accounts.new_edition.to_account_info(),
+                master_edition: ctx.accounts.master_edition.to_account_info(),
+                new_mint: ctx.accounts.new_mint.to_account_info(),
+                edition_mark_pda: ctx.accounts.edition_marker.to_account_info(),
+                new_mint_authority: ctx.accounts.collection_mint.to_account_info(),
+                payer: ctx.accounts.payer.to_account_info(),
+                token_account_owner: ctx.accounts.master_holder.to_account_info(),
+                token_account: ctx.accounts.master_token_account.to_account_info(),
+                new_metadata_update_authority: ctx.accounts.collection_mint.to_account_info(),
+                metadata: ctx.accounts.master_metadata_account.to_account_info(),
+                token_program: ctx.accounts.token_program.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                rent: ctx.accounts.rent.to_account_info(),
+                metadata_mint: ctx.accounts.master_mint.to_account_info(),
+            },
+            signer_seeds
+        ),
+        edition,
+    )?;
+
+    emit!(PrintEditionEvent {
+        collection_id,
+        master_mint: ctx.accounts.master_mint.key(),
+        mint: ctx.accounts.new_mint.key(),
+        owner: ctx.accounts.payer.key(),
+        edition,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(collection_id: u64)]
+pub struct PrintEdition<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub master_holder: Signer<'info>,
+
+    #[account(
+        seeds = [COLLECTION_SEED, collection_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub collection_mint: Box<Account<'info, Mint>>,
+
+    pub master_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        token::mint = master_mint,
+        token::authority = master_holder,
+    )]
+    pub master_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        address = find_metadata_account(&master_mint.key()).0,
+        constraint = master_metadata_account.update_authority == collection_mint.key() @ CollectionError::InvalidCollection,
+    )]
+    pub master_metadata_account: Box<Account<'info, MetadataAccount>>,
+
+    #[account(
+        mut,
+        address = find_master_edition_account(&master_mint.key()).0
+    )]
+    pub master_edition: Box<Account<'info, MasterEditionAccount>>,
+
+    /// CHECK: checked against the next edition number
+    #[account(mut)]
+    pub edition_marker: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        mint::decimals = 0,
+        mint::authority = collection_mint,
+        mint::freeze_authority = collection_mint,
+    )]
+    pub new_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = new_mint,
+        associated_token::authority = payer,
+    )]
+    pub new_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: address
+    #[account(
+        mut,
+        address = find_metadata_account(&new_mint.key()).0
+    )]
+    pub new_metadata_account: UncheckedAccount<'info>,
+
+    /// CHECK: address
+    #[account(
+        mut,
+        address = find_master_edition_account(&new_mint.key()).0
+    )]
+    pub new_edition: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub metadata_program: Program<'info, Metadata>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[event]
+pub struct PrintEditionEvent {
+    pub collection_id: u64,
+    pub master_mint: Pubkey,
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub edition: u64,
+}
+
+// anyone can crank the reveal, every uri is checked against the provenance hash committed at collection creation
+pub fn reveal(ctx: Context<Reveal>, collection_id: u64, uri: String, proof: Vec<[u8; 32]>) -> Result<()> {
+    let collection_id_bytes = collection_id.to_le_bytes();
+    let signer_seeds: &[&[&[u8]]] = &[&[COLLECTION_SEED, &collection_id_bytes, &[ctx.bumps.collection_mint]]];
+
+    let reveal = ctx.accounts.collection_info.reveal.clone().ok_or(CollectionError::RevealNotEnabled)?;
+
+    // the placeholder uri carries the token number the item was minted with
+    let current = &ctx.accounts.metadata_account;
+    let token_number = current.uri.trim_end_matches('\0')
+        .strip_prefix(reveal.placeholder_uri.as_str())
+        .and_then(|rest| rest.strip_suffix(".json"))
+        .and_then(|number| number.parse::<u64>().ok())
+        .ok_or(CollectionError::AlreadyRevealed)?;
+
+    let leaf = hash::hashv(&[&token_number.to_le_bytes(), uri.as_bytes()]).to_bytes();
+    require!(
+        utils::verify_sha256_merkle_proof(&proof, &reveal.provenance_hash, leaf),
+        CollectionError::InvalidProvenanceProof
+    );
+
+    let data = Data {
+        name: current.name.trim_end_matches('\0').to_string(),
+        symbol: current.symbol.trim_end_matches('\0').to_string(),
+        uri: uri.clone(),
+        seller_fee_basis_points: current.seller_fee_basis_points,
+        creators: current.creators.clone(),
+    };
+
+    msg!("revealing nft");
+
+    // update v1 handles programmable nfts as well as plain ones
+    let metadata_program = ctx.accounts.metadata_program.to_account_info();
+    let collection_mint = ctx.accounts.collection_mint.to_account_info();
+    let mint = ctx.accounts.mint.to_account_info();
+    let metadata_account = ctx.accounts.metadata_account.to_account_info();
+    let payer = ctx.accounts.payer.to_account_info();
+    let system_program = ctx.accounts.system_program.to_account_info();
+    UpdateV1CpiBuilder::new(&metadata_program)
+        .authority(&collection_mint)
+        .mint(&mint)
+        .metadata(&metadata_account)
+        .payer(&payer)
+        .system_program(&system_program)
+        .sysvar_instructions(&ctx.accounts.sysvar_instructions)
+        .data(data)
+        .invoke_signed(signer_seeds)?;
+
+    emit!(RevealNftEvent {
+        collection_id,
+        mint: ctx.accounts.mint.key(),
+        token_number,
+        uri,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(collection_id: u64)]
+pub struct Reveal<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        seeds = [COLLECTION_INFO, collection_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub collection_info: Box<Account<'info, collection::CollectionInfo>>,
+
+    #[account(
+        seeds = [COLLECTION_SEED, collection_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub collection_mint: Box<Account<'info, Mint>>,
+
+    pub mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        address = find_metadata_account(&mint.key()).0,
+        constraint = metadata_account.collection.as_ref().is_some_and(|c| c.verified && c.key == collection_mint.key()) @ CollectionError::InvalidCollection,
+    )]
+    pub metadata_account: Box<Account<'info, MetadataAccount>>,
+
+    /// CHECK: address
+    #[account(address = IX_ID)]
+    pub sysvar_instructions: UncheckedAccount<'info>,
+
+    /// CHECK: address
+    #[account(address = mpl_token_metadata::ID)]
+    pub metadata_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event]
+pub struct RevealNftEvent {
+    pub collection_id: u64,
+    pub mint: Pubkey,
+    pub token_number: u64,
+    pub uri: String,
+}
@@ -0,0 +1,469 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{program::invoke, system_instruction};
+use anchor_spl::token;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::metadata::MetadataAccount;
+use anchor_spl::token::{Mint, Token, TokenAccount, Transfer};
+use crate::states::{collection_bid, init, offer};
+use crate::constants::{COLLECTION_BID_SEED, COLLECTION_SEED, INIT_SEED, OFFER_SEED};
+use crate::errors::{CollectionError, MarketPlaceError};
+use crate::instructions::marketplace::split_sale;
+use crate::instructions::spl_token::find_metadata_account;
+
+pub fn make_offer(ctx: Context<MakeOffer>, collection_id: u64, amount: u64) -> Result<()> {
+    require!(amount > 0, MarketPlaceError::InvalidPrice);
+    require!(ctx.accounts.bidder.lamports() >= amount, MarketPlaceError::InsufficientBalance);
+
+    let offer = &mut ctx.accounts.offer;
+    offer.collection_id = collection_id;
+    offer.mint = ctx.accounts.mint.key();
+    offer.bidder = ctx.accounts.bidder.key();
+    offer.amount = amount;
+
+    msg!("escrowing offer");
+
+    invoke(
+        &system_instruction::transfer(&ctx.accounts.bidder.key(), &ctx.accounts.offer.key(), amount),
+        &[
+            ctx.accounts.bidder.to_account_info(),
+            ctx.accounts.offer.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+        ],
+    )?;
+
+    emit!(MakeOfferEvent {
+        collection_id,
+        mint: ctx.accounts.mint.key(),
+        bidder: ctx.accounts.bidder.key(),
+        amount,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(collection_id: u64)]
+pub struct MakeOffer<'info> {
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+
+    #[account(
+        seeds = [COLLECTION_SEED, collection_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub collection_mint: Box<Account<'info, Mint>>,
+
+    pub mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        address = find_metadata_account(&mint.key()).0,
+        constraint = metadata_account.collection.as_ref().is_some_and(|c| c.verified && c.key == collection_mint.key()) @ CollectionError::InvalidCollection,
+    )]
+    pub metadata_account: Box<Account<'info, MetadataAccount>>,
+
+    #[account(
+        init,
+        payer = bidder,
+        space = 8 + offer::Offer::INIT_SPACE,
+        seeds = [OFFER_SEED, mint.key().as_ref(), bidder.key().as_ref()],
+        bump,
+    )]
+    pub offer: Box<Account<'info, offer::Offer>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event]
+pub struct MakeOfferEvent {
+    pub collection_id: u64,
+    pub mint: Pubkey,
+    pub bidder: Pubkey,
+    pub amount: u64,
+}
+
+// closing the offer refunds the escrow together with the rent
+pub fn cancel_offer(ctx: Context<CancelOffer>) -> Result<()> {
+    let offer = &ctx.accounts.offer;
+
+    emit!(CancelOfferEvent {
+        collection_id: offer.collection_id,
+        mint: offer.mint,
+        bidder: offer.bidder,
+        amount: offer.amount,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CancelOffer<'info> {
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [OFFER_SEED, offer.mint.as_ref(), bidder.key().as_ref()],
+        bump,
+        has_one = bidder,
+        close = bidder,
+    )]
+    pub offer: Box<Account<'info, offer::Offer>>,
+}
+
+#[event]
+pub struct CancelOfferEvent {
+    pub collection_id: u64,
+    pub mint: Pubkey,
+    pub bidder: Pubkey,
+    pub amount: u64,
+}
+
+// the holder accepts from its wallet, a listed nft has to go through cancel_listing first in the same transaction.
+// remaining_accounts holds the metadata creators in order
+pub fn accept_offer<'info>(ctx: Context<'_, '_, 'info, 'info, AcceptOffer<'info>>) -> Result<()> {
+    let amount = ctx.accounts.offer.amount;
+
+    msg!("paying platform fee, royalties and seller from the offer escrow");
+
+    let escrow = ctx.accounts.offer.to_account_info();
+    let split = split_sale(
+        amount,
+        ctx.accounts.init.fee_basis_points,
+        &ctx.accounts.metadata_account,
+        ctx.remaining_accounts,
+        &ctx.accounts.fee_vault.to_account_info(),
+        &ctx.accounts.seller.to_account_info(),
+        |to, lamports| {
+            **escrow.try_borrow_mut_lamports()? -= lamports;
+            **to.try_borrow_mut_lamports()? += lamports;
+            Ok(())
+        },
+    )?;
+
+    msg!("moving nft to the bidder");
+
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.seller_token_account.to_account_info(),
+                to: ctx.accounts.bidder_token_account.to_account_info(),
+                authority: ctx.accounts.seller.to_account_info(),
+            },
+        ),
+        1,
+    )?;
+
+    let offer = &ctx.accounts.offer;
+    emit!(AcceptOfferEvent {
+        collection_id: offer.collection_id,
+        mint: offer.mint,
+        seller: ctx.accounts.seller.key(),
+        bidder: offer.bidder,
+        amount,
+        fee: split.fee,
+        royalties: split.royalties,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AcceptOffer<'info> {
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    /// CHECK: address
+    #[account(mut)]
+    pub bidder: UncheckedAccount<'info>,
+
+    pub mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [OFFER_SEED, mint.key().as_ref(), bidder.key().as_ref()],
+        bump,
+        has_one = bidder,
+        has_one = mint,
+        close = bidder,
+    )]
+    pub offer: Box<Account<'info, offer::Offer>>,
+
+    #[account(
+        address = find_metadata_account(&mint.key()).0,
+    )]
+    pub metadata_account: Box<Account<'info, MetadataAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = seller,
+    )]
+    pub seller_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = seller,
+        associated_token::mint = mint,
+        associated_token::authority = bidder,
+    )]
+    pub bidder_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [INIT_SEED],
+        bump,
+    )]
+    pub init: Box<Account<'info, init::Init>>,
+
+    /// CHECK: address
+    #[account(
+        mut,
+        address = init.vault,
+    )]
+    pub fee_vault: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event]
+pub struct AcceptOfferEvent {
+    pub collection_id: u64,
+    pub mint: Pubkey,
+    pub seller: Pubkey,
+    pub bidder: Pubkey,
+    pub amount: u64,
+    pub fee: u64,
+    pub royalties: u64,
+}
+
+pub fn make_collection_bid(ctx: Context<MakeCollectionBid>, collection_id: u64, price: u64, quantity: u64) -> Result<()> {
+    require!(price > 0, MarketPlaceError::InvalidPrice);
+    require!(quantity > 0, MarketPlaceError::InvalidQuantity);
+
+    let amount = price.checked_mul(quantity).ok_or(MarketPlaceError::InvalidQuantity)?;
+    require!(ctx.accounts.bidder.lamports() >= amount, MarketPlaceError::InsufficientBalance);
+
+    let collection_bid = &mut ctx.accounts.collection_bid;
+    collection_bid.collection_id = collection_id;
+    collection_bid.bidder = ctx.accounts.bidder.key();
+    collection_bid.price = price;
+    collection_bid.quantity = quantity;
+
+    msg!("escrowing collection bid");
+
+    invoke(
+        &system_instruction::transfer(&ctx.accounts.bidder.key(), &ctx.accounts.collection_bid.key(), amount),
+        &[
+            ctx.accounts.bidder.to_account_info(),
+            ctx.accounts.collection_bid.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+        ],
+    )?;
+
+    emit!(MakeCollectionBidEvent {
+        collection_id,
+        bidder: ctx.accounts.bidder.key(),
+        price,
+        quantity,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(collection_id: u64)]
+pub struct MakeCollectionBid<'info> {
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+
+    #[account(
+        init,
+        payer = bidder,
+        space = 8 + collection_bid::CollectionBid::INIT_SPACE,
+        seeds = [COLLECTION_BID_SEED, collection_id.to_le_bytes().as_ref(), bidder.key().as_ref()],
+        bump,
+    )]
+    pub collection_bid: Box<Account<'info, collection_bid::CollectionBid>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event]
+pub struct MakeCollectionBidEvent {
+    pub collection_id: u64,
+    pub bidder: Pubkey,
+    pub price: u64,
+    pub quantity: u64,
+}
+
+// closing the bid refunds the escrow left for the unfilled items together with the rent
+pub fn cancel_collection_bid(ctx: Context<CancelCollectionBid>) -> Result<()> {
+    let collection_bid = &ctx.accounts.collection_bid;
+
+    emit!(CancelCollectionBidEvent {
+        collection_id: collection_bid.collection_id,
+        bidder: collection_bid.bidder,
+        quantity: collection_bid.quantity,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CancelCollectionBid<'info> {
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [COLLECTION_BID_SEED, collection_bid.collection_id.to_le_bytes().as_ref(), bidder.key().as_ref()],
+        bump,
+        has_one = bidder,
+        close = bidder,
+    )]
+    pub collection_bid: Box<Account<'info, collection_bid::CollectionBid>>,
+}
+
+#[event]
+pub struct CancelCollectionBidEvent {
+    pub collection_id: u64,
+    pub bidder: Pubkey,
+    pub quantity: u64,
+}
+
+// any holder of a verified collection item can fill one unit of the bid, the bid closes on the last one.
+// remaining_accounts holds the metadata creators in order
+pub fn sell_into_collection_bid<'info>(ctx: Context<'_, '_, 'info, 'info, SellIntoCollectionBid<'info>>, collection_id: u64) -> Result<()> {
+    let price = ctx.accounts.collection_bid.price;
+
+    msg!("paying platform fee, royalties and seller from the collection bid escrow");
+
+    let escrow = ctx.accounts.collection_bid.to_account_info();
+    let split = split_sale(
+        price,
+        ctx.accounts.init.fee_basis_points,
+        &ctx.accounts.metadata_account,
+        ctx.remaining_accounts,
+        &ctx.accounts.fee_vault.to_account_info(),
+        &ctx.accounts.seller.to_account_info(),
+        |to, lamports| {
+            **escrow.try_borrow_mut_lamports()? -= lamports;
+            **to.try_borrow_mut_lamports()? += lamports;
+            Ok(())
+        },
+    )?;
+
+    msg!("moving nft to the bidder");
+
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.seller_token_account.to_account_info(),
+                to: ctx.accounts.bidder_token_account.to_account_info(),
+                authority: ctx.accounts.seller.to_account_info(),
+            },
+        ),
+        1,
+    )?;
+
+    let collection_bid = &mut ctx.accounts.collection_bid;
+    collection_bid.quantity -= 1;
+    let remaining = collection_bid.quantity;
+
+    emit!(SellIntoCollectionBidEvent {
+        collection_id,
+        mint: ctx.accounts.mint.key(),
+        seller: ctx.accounts.seller.key(),
+        bidder: ctx.accounts.bidder.key(),
+        price,
+        fee: split.fee,
+        royalties: split.royalties,
+        remaining,
+    });
+
+    if remaining == 0 {
+        ctx.accounts.collection_bid.close(ctx.accounts.bidder.to_account_info())?;
+    }
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(collection_id: u64)]
+pub struct SellIntoCollectionBid<'info> {
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    /// CHECK: address
+    #[account(mut)]
+    pub bidder: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [COLLECTION_BID_SEED, collection_id.to_le_bytes().as_ref(), bidder.key().as_ref()],
+        bump,
+        has_one = bidder,
+    )]
+    pub collection_bid: Box<Account<'info, collection_bid::CollectionBid>>,
+
+    #[account(
+        seeds = [COLLECTION_SEED, collection_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub collection_mint: Box<Account<'info, Mint>>,
+
+    pub mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        address = find_metadata_account(&mint.key()).0,
+        constraint = metadata_account.collection.as_ref().is_some_and(|c| c.verified && c.key == collection_mint.key()) @ CollectionError::InvalidCollection,
+    )]
+    pub metadata_account: Box<Account<'info, MetadataAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = seller,
+    )]
+    pub seller_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = seller,
+        associated_token::mint = mint,
+        associated_token::authority = bidder,
+    )]
+    pub bidder_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [INIT_SEED],
+        bump,
+    )]
+    pub init: Box<Account<'info, init::Init>>,
+
+    /// CHECK: address
+    #[account(
+        mut,
+        address = init.vault,
+    )]
+    pub fee_vault: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event]
+pub struct SellIntoCollectionBidEvent {
+    pub collection_id: u64,
+    pub mint: Pubkey,
+    pub seller: Pubkey,
+    pub bidder: Pubkey,
+    pub price: u64,
+    pub fee: u64,
+    pub royalties: u64,
+    pub remaining: u64,
+}
@@ -0,0 +1,403 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::{get_associated_token_address, AssociatedToken};
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use mpl_token_metadata::accounts::TokenRecord;
+use mpl_token_metadata::instructions::{
+    CreateV1CpiBuilder, DelegateCpiBuilder, MintV1CpiBuilder, RevokeCpiBuilder, TransferV1CpiBuilder,
+    VerifyCollectionV1CpiBuilder,
+};
+use mpl_token_metadata::types::{Collection, DelegateArgs, PrintSupply, RevokeArgs, TokenStandard};
+use solana_program::sysvar::instructions::ID as IX_ID;
+use crate::constants::{AUTH_RULES_PROGRAM_ID, COLLECTION_SEED};
+use crate::errors::CollectionError;
+use crate::instructions::spl_token::{charge_collection_mint, check_mint_phase, collection_creators, collection_item_uri, find_master_edition_account, find_metadata_account, MintNft, MintNftEvent};
+// generated by #[derive(Accounts)] on MintNft, needed to nest it in MintPnft
+use crate::instructions::spl_token::{__client_accounts_mint_nft, __cpi_client_accounts_mint_nft, MintNftBumps};
+use crate::states::collection;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum PnftDelegateKind {
+    Transfer,
+    Utility,
+    Staking,
+}
+
+pub fn mint_pnft(ctx: Context<MintPnft>, collection_id: u64, name: String, symbol: String, uri: String) -> Result<()> {
+    let collection_id_bytes = collection_id.to_le_bytes();
+    let signer_seeds: &[&[&[u8]]] = &[&[COLLECTION_SEED, &collection_id_bytes, &[ctx.bumps.mint_nft.collection_mint]]];
+
+    let rule_set = ctx.accounts.mint_nft.collection_info.rule_set;
+    require!(
+        rule_set == ctx.accounts.authorization_rules.as_ref().map(|rules| rules.key()),
+        CollectionError::InvalidRuleSet
+    );
+
+    let charge = check_mint_phase(&mut ctx.accounts.mint_nft, collection::PhaseKind::Public)?;
+    let token_number = charge_collection_mint(&mut ctx.accounts.mint_nft, collection_id, charge)?;
+
+    let accounts = &ctx.accounts.mint_nft;
+    let collection_mint = accounts.collection_mint.to_account_info();
+    let metadata_program = accounts.metadata_program.to_account_info();
+    let mint = accounts.mint.to_account_info();
+    let user = accounts.user.to_account_info();
+    let system_program = accounts.system_program.to_account_info();
+    let token_program = accounts.token_program.to_account_info();
+    let authorization_rules_program = ctx.accounts.authorization_rules_program.as_ref().map(|program| program.to_account_info());
+    let authorization_rules = ctx.accounts.authorization_rules.as_ref().map(|rules| rules.to_account_info());
+
+    let print_supply = match accounts.collection_info.edition_max_supply {
+        0 => PrintSupply::Zero,
+        max_supply => PrintSupply::Limited(max_supply),
+    };
+
+    msg!("creating programmable nft metadata");
+
+    let mut create = CreateV1CpiBuilder::new(&metadata_program);
+    create
+        .metadata(&accounts.metadata_account)
+        .master_edition(Some(&accounts.master_edition))
+        .mint(&mint, false)
+        .authority(&collection_mint)
+        .payer(&user)
+        .update_authority(&collection_mint, true)
+        .system_program(&system_program)
+        .sysvar_instructions(&ctx.accounts.sysvar_instructions)
+        .spl_token_program(Some(&token_program))
+        .name(name)
+        .symbol(symbol)
+        .uri(collection_item_uri(&accounts.collection_info, token_number, uri))
+        .seller_fee_basis_points(accounts.collection_info.seller_fee_basis_points)
+        .creators(collection_creators(collection_mint.key(), &accounts.collection_info.creators))
+        .primary_sale_happened(false)
+        .is_mutable(true)
+        .token_standard(TokenStandard::ProgrammableNonFungible)
+        .collection(Collection { verified: false, key: collection_mint.key() })
+        .print_supply(print_supply);
+    if let Some(rule_set) = rule_set {
+        create.rule_set(rule_set);
+    }
+    create.invoke_signed(signer_seeds)?;
+
+    msg!("minting programmable nft");
+
+    MintV1CpiBuilder::new(&metadata_program)
+        .token(&accounts.nft_account_to.to_account_info())
+        .token_owner(Some(&user))
+        .metadata(&accounts.metadata_account)
+        .master_edition(Some(&accounts.master_edition))
+        .token_record(Some(&ctx.accounts.token_record))
+        .mint(&mint)
+        .authority(&collection_mint)
+        .payer(&user)
+        .system_program(&system_program)
+        .sysvar_instructions(&ctx.accounts.sysvar_instructions)
+        .spl_token_program(&token_program)
+        .spl_ata_program(&accounts.associated_token_program.to_account_info())
+        .authorization_rules_program(authorization_rules_program.as_ref())
+        .authorization_rules(authorization_rules.as_ref())
+        .amount(1)
+        .invoke_signed(signer_seeds)?;
+
+    VerifyCollectionV1CpiBuilder::new(&metadata_program)
+        .authority(&collection_mint)
+        .metadata(&accounts.metadata_account)
+        .collection_mint(&collection_mint)
+        .collection_metadata(Some(&accounts.collection_metadata_account))
+        .collection_master_edition(Some(&accounts.collection_master_edition_account))
+        .system_program(&system_program)
+        .sysvar_instructions(&ctx.accounts.sysvar_instructions)
+        .invoke_signed(signer_seeds)?;
+
+    emit!(MintNftEvent {
+        collection_id,
+        mint: accounts.mint.key(),
+        owner: accounts.user.key(),
+        token_number,
+        price: charge.price,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(collection_id: u64)]
+pub struct MintPnft<'info> {
+    pub mint_nft: MintNft<'info>,
+
+    /// CHECK: address
+    #[account(
+        mut,
+        address = TokenRecord::find_pda(&mint_nft.mint.key(), &mint_nft.nft_account_to.key()).0
+    )]
+    pub token_record: UncheckedAccount<'info>,
+
+    /// CHECK: address
+    #[account(address = IX_ID)]
+    pub sysvar_instructions: UncheckedAccount<'info>,
+
+    /// CHECK: address
+    #[account(address = AUTH_RULES_PROGRAM_ID)]
+    pub authorization_rules_program: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: checked against the collection rule set
+    pub authorization_rules: Option<UncheckedAccount<'info>>,
+}
+
+pub fn transfer_pnft(ctx: Context<TransferPnft>) -> Result<()> {
+    let authorization_rules_program = ctx.accounts.authorization_rules_program.as_ref().map(|program| program.to_account_info());
+    let authorization_rules = ctx.accounts.authorization_rules.as_ref().map(|rules| rules.to_account_info());
+
+    msg!("transferring programmable nft");
+
+    TransferV1CpiBuilder::new(&ctx.accounts.metadata_program.to_account_info())
+        .token(&ctx.accounts.token_account.to_account_info())
+        .token_owner(&ctx.accounts.owner.to_account_info())
+        .destination_token(&ctx.accounts.destination_token_account)
+        .destination_owner(&ctx.accounts.destination_owner)
+        .mint(&ctx.accounts.mint.to_account_info())
+        .metadata(&ctx.accounts.metadata_account)
+        .edition(Some(&ctx.accounts.master_edition))
+        .token_record(Some(&ctx.accounts.token_record))
+        .destination_token_record(Some(&ctx.accounts.destination_token_record))
+        .authority(&ctx.accounts.owner.to_account_info())
+        .payer(&ctx.accounts.owner.to_account_info())
+        .system_program(&ctx.accounts.system_program.to_account_info())
+        .sysvar_instructions(&ctx.accounts.sysvar_instructions)
+        .spl_token_program(&ctx.accounts.token_program.to_account_info())
+        .spl_ata_program(&ctx.accounts.associated_token_program.to_account_info())
+        .authorization_rules_program(authorization_rules_program.as_ref())
+        .authorization_rules(authorization_rules.as_ref())
+        .amount(1)
+        .invoke()?;
+
+    emit!(TransferPnftEvent {
+        mint: ctx.accounts.mint.key(),
+        from: ctx.accounts.owner.key(),
+        to: ctx.accounts.destination_owner.key(),
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct TransferPnft<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// CHECK: any wallet
+    pub destination_owner: UncheckedAccount<'info>,
+
+    pub mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = owner,
+    )]
+    pub token_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: address, created by token metadata when missing
+    #[account(
+        mut,
+        address = get_associated_token_address(&destination_owner.key(), &mint.key())
+    )]
+    pub destination_token_account: UncheckedAccount<'info>,
+
+    /// CHECK: address
+    #[account(
+        mut,
+        address = find_metadata_account(&mint.key()).0
+    )]
+    pub metadata_account: UncheckedAccount<'info>,
+
+    /// CHECK: address
+    #[account(
+        address = find_master_edition_account(&mint.key()).0
+    )]
+    pub master_edition: UncheckedAccount<'info>,
+
+    /// CHECK: address
+    #[account(
+        mut,
+        address = TokenRecord::find_pda(&mint.key(), &token_account.key()).0
+    )]
+    pub token_record: UncheckedAccount<'info>,
+
+    /// CHECK: address
+    #[account(
+        mut,
+        address = TokenRecord::find_pda(&mint.key(), &destination_token_account.key()).0
+    )]
+    pub destination_token_record: UncheckedAccount<'info>,
+
+    /// CHECK: address
+    #[account(address = IX_ID)]
+    pub sysvar_instructions: UncheckedAccount<'info>,
+
+    /// CHECK: address
+    #[account(address = AUTH_RULES_PROGRAM_ID)]
+    pub authorization_rules_program: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: validated by token metadata against the nft rule set
+    pub authorization_rules: Option<UncheckedAccount<'info>>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    /// CHECK: address
+    #[account(address = mpl_token_metadata::ID)]
+    pub metadata_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event]
+pub struct TransferPnftEvent {
+    pub mint: Pubkey,
+    pub from: Pubkey,
+    pub to: Pubkey,
+}
+
+pub fn delegate_pnft(ctx: Context<DelegatePnft>, kind: PnftDelegateKind) -> Result<()> {
+    msg!("delegating programmable nft");
+
+    invoke_pnft_delegate(ctx.accounts, kind, false)
+}
+
+pub fn revoke_pnft_delegate(ctx: Context<DelegatePnft>, kind: PnftDelegateKind) -> Result<()> {
+    msg!("revoking programmable nft delegate");
+
+    invoke_pnft_delegate(ctx.accounts, kind, true)
+}
+
+// token delegates live in the token record, so no delegate record is passed
+fn invoke_pnft_delegate(accounts: &DelegatePnft, kind: PnftDelegateKind, revoke: bool) -> Result<()> {
+    let metadata_program = accounts.metadata_program.to_account_info();
+    let owner = accounts.owner.to_account_info();
+    let mint = accounts.mint.to_account_info();
+    let token = accounts.token_account.to_account_info();
+    let system_program = accounts.system_program.to_account_info();
+    let token_program = accounts.token_program.to_account_info();
+    let authorization_rules_program = accounts.authorization_rules_program.as_ref().map(|program| program.to_account_info());
+    let authorization_rules = accounts.authorization_rules.as_ref().map(|rules| rules.to_account_info());
+
+    if revoke {
+        let revoke_args = match kind {
+            PnftDelegateKind::Transfer => RevokeArgs::TransferV1,
+            PnftDelegateKind::Utility => RevokeArgs::UtilityV1,
+            PnftDelegateKind::Staking => RevokeArgs::StakingV1,
+        };
+
+        RevokeCpiBuilder::new(&metadata_program)
+            .delegate(&accounts.delegate)
+            .metadata(&accounts.metadata_account)
+            .master_edition(Some(&accounts.master_edition))
+            .token_record(Some(&accounts.token_record))
+            .mint(&mint)
+            .token(Some(&token))
+            .authority(&owner)
+            .payer(&owner)
+            .system_program(&system_program)
+            .sysvar_instructions(&accounts.sysvar_instructions)
+            .spl_token_program(Some(&token_program))
+            .authorization_rules_program(authorization_rules_program.as_ref())
+            .authorization_rules(authorization_rules.as_ref())
+            .revoke_args(revoke_args)
+            .invoke()?;
+    } else {
+        let delegate_args = match kind {
+            PnftDelegateKind::Transfer => DelegateArgs::TransferV1 { amount: 1, authorization_data: None },
+            PnftDelegateKind::Utility => DelegateArgs::UtilityV1 { amount: 1, authorization_data: None },
+            PnftDelegateKind::Staking => DelegateArgs::StakingV1 { amount: 1, authorization_data: None },
+        };
+
+        DelegateCpiBuilder::new(&metadata_program)
+            .delegate(&accounts.delegate)
+            .metadata(&accounts.metadata_account)
+            .master_edition(Some(&accounts.master_edition))
+            .token_record(Some(&accounts.token_record))
+            .mint(&mint)
+            .token(Some(&token))
+            .authority(&owner)
+            .payer(&owner)
+            .system_program(&system_program)
+            .sysvar_instructions(&accounts.sysvar_instructions)
+            .spl_token_program(Some(&token_program))
+            .authorization_rules_program(authorization_rules_program.as_ref())
+            .authorization_rules(authorization_rules.as_ref())
+            .delegate_args(delegate_args)
+            .invoke()?;
+    }
+
+    emit!(DelegatePnftEvent {
+        mint: accounts.mint.key(),
+        owner: accounts.owner.key(),
+        delegate: accounts.delegate.key(),
+        kind,
+        revoked: revoke,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct DelegatePnft<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// CHECK: any wallet or program PDA
+    pub delegate: UncheckedAccount<'info>,
+
+    pub mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = owner,
+    )]
+    pub token_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: address
+    #[account(
+        mut,
+        address = find_metadata_account(&mint.key()).0
+    )]
+    pub metadata_account: UncheckedAccount<'info>,
+
+    /// CHECK: address
+    #[account(
+        address = find_master_edition_account(&mint.key()).0
+    )]
+    pub master_edition: UncheckedAccount<'info>,
+
+    /// CHECK: address
+    #[account(
+        mut,
+        address = TokenRecord::find_pda(&mint.key(), &token_account.key()).0
+    )]
+    pub token_record: UncheckedAccount<'info>,
+
+    /// CHECK: address
+    #[account(address = IX_ID)]
+    pub sysvar_instructions: UncheckedAccount<'info>,
+
+    /// CHECK: address
+    #[account(address = AUTH_RULES_PROGRAM_ID)]
+    pub authorization_rules_program: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: validated by token metadata against the nft rule set
+    pub authorization_rules: Option<UncheckedAccount<'info>>,
+
+    pub token_program: Program<'info, Token>,
+    /// CHECK: address
+    #[account(address = mpl_token_metadata::ID)]
+    pub metadata_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event]
+pub struct DelegatePnftEvent {
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub delegate: Pubkey,
+    pub kind: PnftDelegateKind,
+    pub revoked: bool,
+}
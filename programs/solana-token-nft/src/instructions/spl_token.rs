@@ -1,19 +1,24 @@
 use anchor_lang::prelude::*;
-use anchor_lang::solana_program::system_instruction;
+use anchor_lang::solana_program::{program::invoke, system_instruction};
+use anchor_lang::system_program::{self, CreateAccount};
 use anchor_spl::{token, metadata};
-use anchor_spl::token::{Token, MintTo, TokenAccount, Mint, SetAuthority, spl_token::instruction::AuthorityType, Burn};
+use anchor_spl::token::{Token, MintTo, TokenAccount, Mint};
+use anchor_spl::token_interface::{self, TokenInterface, Token2022, spl_token_2022::extension::ExtensionType, spl_token_2022::instruction::AuthorityType};
+use anchor_spl::token_interface::spl_pod::optional_keys::OptionalNonZeroPubkey;
+use anchor_spl::token_interface::spl_token_metadata_interface::state::TokenMetadata;
 use anchor_spl::associated_token::AssociatedToken;
 use anchor_spl::metadata::{CreateMasterEditionV3, CreateMetadataAccountsV3, Metadata, SetAndVerifySizedCollectionItem, SignMetadata};
-use mpl_token_metadata::types::{CollectionDetails, Collection, Creator, DataV2};
-use crate::states::{init};
+use mpl_token_metadata::types::{CollectionDetails, Collection, Creator, DataV2, UseMethod, Uses};
+use crate::states::{init, collection, token_count, voucher, wallet_mint};
 use crate::utils;
-use crate::constants::{ COLLECTION_SEED, EDITION_SEED, INIT_SEED, METADATA_SEED, COLLECTION_INFO, TOKEN_SEED};
-use crate::errors::{MarketPlaceError, SigError};
+use crate::instructions::{collection_config, dutch_auction, marketplace};
+use crate::constants::{ COLLECTION_SEED, EDITION_SEED, INIT_SEED, MAX_MINT_PHASES, METADATA_SEED, COLLECTION_INFO, TOKEN_COUNT_SEED, TOKEN_SEED, VOUCHER_SEED, WALLET_MINT_SEED};
+use crate::errors::{CollectionError, MarketPlaceError, SigError};
 use solana_program::instruction::Instruction;
 use solana_program::sysvar::instructions::{ID as IX_ID, load_instruction_at_checked};
 use solana_program::keccak;
 
-fn find_metadata_account(mint: &Pubkey) -> (Pubkey, u8) {
+pub(crate) fn find_metadata_account(mint: &Pubkey) -> (Pubkey, u8) {
     Pubkey::find_program_address(
         &[
             METADATA_SEED,
@@ -22,7 +27,7 @@ fn find_metadata_account(mint: &Pubkey) -> (Pubkey, u8) {
         ], &mpl_token_metadata::ID)
 }
 
-fn find_master_edition_account(mint: &Pubkey) -> (Pubkey, u8) {
+pub(crate) fn find_master_edition_account(mint: &Pubkey) -> (Pubkey, u8) {
     Pubkey::find_program_address(
         &[
             METADATA_SEED,
@@ -32,6 +37,46 @@ fn find_master_edition_account(mint: &Pubkey) -> (Pubkey, u8) {
         ], &mpl_token_metadata::ID)
 }
 
+// metadata creators for collection items: the signing collection PDA first, then the configured splits
+pub(crate) fn collection_creators(collection_mint: Pubkey, creators: &[collection::CreatorShare]) -> Vec<Creator> {
+    let mut metadata_creators = vec![Creator {
+        address: collection_mint,
+        verified: true,
+        share: if creators.is_empty() { 100 } else { 0 },
+    }];
+
+    for creator in creators.iter() {
+        metadata_creators.push(Creator {
+            address: creator.address,
+            verified: false,
+            share: creator.share,
+        });
+    }
+
+    metadata_creators
+}
+
+// metaplex uses for collection items, every item starts with all its uses remaining
+pub(crate) fn collection_uses(uses: &Option<collection::ItemUses>) -> Option<Uses> {
+    uses.map(|item_uses| Uses {
+        use_method: match item_uses.use_method {
+            collection::ItemUseMethod::Burn => UseMethod::Burn,
+            collection::ItemUseMethod::Multiple => UseMethod::Multiple,
+            collection::ItemUseMethod::Single => UseMethod::Single,
+        },
+        remaining: item_uses.total,
+        total: item_uses.total,
+    })
+}
+
+// delayed reveal collections mint every item with its numbered placeholder uri
+pub(crate) fn collection_item_uri(collection_info: &collection::CollectionInfo, token_number: u64, uri: String) -> String {
+    match &collection_info.reveal {
+        Some(reveal) => format!("{}{}.json", reveal.placeholder_uri, token_number),
+        None => uri,
+    }
+}
+
 pub fn initialize(ctx: Context<Initialize>, vault: Pubkey) -> Result<()> {
     let init = &mut ctx.accounts.init;
     init.admin = ctx.accounts.admin.key();
@@ -55,8 +100,30 @@ pub struct Initialize<'info> {
     pub system_program: Program<'info, System>
 }
 
-pub fn create_collection(ctx: Context<CreateCollection>, name: String, symbol: String, uri: String) -> Result<()> {
-    let signer_seeds : &[&[&[u8]]] = &[&[COLLECTION_SEED, &[ctx.bumps.collection_mint]]];
+#[allow(clippy::too_many_arguments)]
+pub fn create_collection(ctx: Context<CreateCollection>, collection_id: u64, authority: Pubkey, max_supply: u64, mint_price: u64, seller_fee_basis_points: u16, creators: Vec<collection::CreatorShare>, name: String, symbol: String, uri: String, reveal: Option<collection::RevealConfig>) -> Result<()> {
+    collection_config::validate_royalties(seller_fee_basis_points, &creators)?;
+    if let Some(reveal) = &reveal {
+        require!(reveal.provenance_hash != [0u8; 32], CollectionError::InvalidRevealConfig);
+        require!(reveal.placeholder_uri.len() <= 180, CollectionError::InvalidRevealConfig);
+    }
+
+    let collection_id_bytes = collection_id.to_le_bytes();
+    let signer_seeds : &[&[&[u8]]] = &[&[COLLECTION_SEED, &collection_id_bytes, &[ctx.bumps.collection_mint]]];
+
+    let collection_info = &mut ctx.accounts.collection_info;
+    collection_info.id = collection_id;
+    collection_info.mint = ctx.accounts.collection_mint.key();
+    collection_info.name = name.clone();
+    collection_info.creator = ctx.accounts.admin.key();
+    collection_info.authority = authority;
+    collection_info.max_supply = max_supply;
+    collection_info.mint_price = mint_price;
+    collection_info.seller_fee_basis_points = seller_fee_basis_points;
+    collection_info.creators = creators.clone();
+    collection_info.reveal = reveal;
+
+    ctx.accounts.token_count.count = 0;
 
     msg!("minting !");
 
@@ -72,15 +139,11 @@ pub fn create_collection(ctx: Context<CreateCollection>, name: String, symbol: S
     msg!("creating metadata account");
 
     let data_v2 = DataV2{
-        name: name,
-        symbol: symbol,
-        uri: uri,
-        seller_fee_basis_points: 0,
-        creators: Some(vec![Creator{
-            address: ctx.accounts.admin.key(),
-            verified: false,
-            share: 100
-        }]),
+        name,
+        symbol,
+        uri,
+        seller_fee_basis_points,
+        creators: Some(collection_creators(ctx.accounts.collection_mint.key(), &creators)),
         collection: None,
         uses: None,
     };
@@ -124,26 +187,37 @@ pub fn create_collection(ctx: Context<CreateCollection>, name: String, symbol: S
                Some(0),
     )?;
 
-    msg!("attach metadata successfully ! and creating sign metadata account");
+    if creators.iter().any(|creator| creator.address == ctx.accounts.admin.key()) {
+        msg!("attach metadata successfully ! and creating sign metadata account");
+
+        metadata::sign_metadata(CpiContext::new(
+            ctx.accounts.metadata_program.to_account_info(),
+            SignMetadata {
+                creator: ctx.accounts.admin.to_account_info(),
+                metadata: ctx.accounts.metadata_account.to_account_info()
+            }
+        ))?;
+    }
+
+    emit!(CreateCollectionEvent {
+        collection_id,
+        collection_mint: ctx.accounts.collection_mint.key(),
+        creator: ctx.accounts.admin.key(),
+        authority,
+    });
 
-    metadata::sign_metadata(CpiContext::new(
-        ctx.accounts.metadata_program.to_account_info(),
-        SignMetadata {
-            creator: ctx.accounts.admin.to_account_info(),
-            metadata: ctx.accounts.metadata_account.to_account_info()
-        }
-    ))?;
     Ok(())
 }
 
 #[derive(Accounts)]
+#[instruction(collection_id: u64)]
 pub struct CreateCollection<'info> {
     #[account(mut)]
     pub admin: Signer<'info>,
 
     #[account(
             init_if_needed,
-            seeds = [COLLECTION_SEED],
+            seeds = [COLLECTION_SEED, collection_id.to_le_bytes().as_ref()],
             bump,
             payer = admin,
             mint::decimals = 0,
@@ -152,6 +226,24 @@ pub struct CreateCollection<'info> {
     )]
     pub collection_mint: Account<'info, Mint>,
 
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + collection::CollectionInfo::INIT_SPACE,
+        seeds = [COLLECTION_INFO, collection_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub collection_info: Box<Account<'info, collection::CollectionInfo>>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + token_count::TokenCount::INIT_SPACE,
+        seeds = [TOKEN_COUNT_SEED, collection_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub token_count: Box<Account<'info, token_count::TokenCount>>,
+
     #[account(
         init_if_needed,
         payer = admin,
@@ -181,39 +273,224 @@ pub struct CreateCollection<'info> {
     pub system_program: Program<'info, System>
 }
 
-pub fn mint_nft(ctx: Context<MintNft>, name: String, symbol: String, uri: String) -> Result<()> {
-    msg!("minting !");
-    let signer_seeds: &[&[&[u8]]] = &[&[COLLECTION_SEED, &[ctx.bumps.collection_mint]]];
+#[event]
+pub struct CreateCollectionEvent {
+    pub collection_id: u64,
+    pub collection_mint: Pubkey,
+    pub creator: Pubkey,
+    pub authority: Pubkey,
+}
+
+pub fn mint_nft(ctx: Context<MintNft>, collection_id: u64, name: String, symbol: String, uri: String) -> Result<()> {
+    let charge = check_mint_phase(ctx.accounts, collection::PhaseKind::Public)?;
+
+    mint_collection_item(ctx.accounts, ctx.bumps.collection_mint, collection_id, charge, name, symbol, uri)
+}
+
+// price a mint path charges, dutch_rebate is only set for prices computed by a rebate dutch auction
+#[derive(Clone, Copy)]
+pub(crate) struct MintCharge {
+    pub price: u64,
+    pub dutch_rebate: bool,
+}
+
+// returns the running phase and its index, None when the collection has no schedule.
+// fails outside every phase and during Closed phases
+pub(crate) fn active_mint_phase(collection_info: &collection::CollectionInfo, now: i64) -> Result<Option<(usize, collection::MintPhase)>> {
+    if collection_info.phases.is_empty() {
+        return Ok(None);
+    }
+
+    let (index, phase) = collection_info.phases.iter()
+        .enumerate()
+        .find(|(_, phase)| phase.start_time <= now && now < phase.end_time)
+        .ok_or(CollectionError::MintPhaseNotActive)?;
+    require!(phase.kind != collection::PhaseKind::Closed, CollectionError::MintClosed);
+
+    Ok(Some((index, *phase)))
+}
+
+// enforces the phase schedule for this mint path and returns the price to charge
+pub(crate) fn check_mint_phase(accounts: &mut MintNft, kind: collection::PhaseKind) -> Result<MintCharge> {
+    let collection_info = &accounts.collection_info;
+    let wallet_mint = &mut accounts.wallet_mint;
+    let now = Clock::get()?.unix_timestamp;
+
+    if wallet_mint.phases_version != collection_info.phases_version {
+        wallet_mint.phase_minted = [0; MAX_MINT_PHASES];
+        wallet_mint.phases_version = collection_info.phases_version;
+    }
+
+    let mut price = collection_info.mint_price;
+    if let Some((index, phase)) = active_mint_phase(collection_info, now)? {
+        require!(phase.kind == kind, CollectionError::MintPhaseNotActive);
+        require!(
+            phase.wallet_limit == 0 || wallet_mint.phase_minted[index] < phase.wallet_limit,
+            CollectionError::WalletLimitReached
+        );
+        wallet_mint.phase_minted[index] += 1;
+        price = phase.price;
+    }
+
+    let mut dutch_rebate = false;
+    if let (collection::PhaseKind::Public, Some(dutch)) = (kind, collection_info.dutch_auction) {
+        price = dutch_auction::dutch_price(&dutch, now)?;
+
+        if dutch.rebate {
+            let token_count = &mut accounts.token_count;
+            // a frozen clearing price ends the drop, later mints would pay less than it
+            require!(token_count.dutch_clearing_price == 0, CollectionError::MintClosed);
+            dutch_rebate = true;
+
+            if wallet_mint.dutch_drop != token_count.dutch_drop {
+                wallet_mint.dutch_minted = 0;
+                wallet_mint.dutch_paid = 0;
+                wallet_mint.rebate_claimed = false;
+                wallet_mint.dutch_drop = token_count.dutch_drop;
+            }
+            wallet_mint.dutch_minted += 1;
+            wallet_mint.dutch_paid += price;
+
+            token_count.dutch_minted += 1;
+            token_count.dutch_paid += price;
+            token_count.dutch_last_price = price;
+        }
+    }
+
+    Ok(MintCharge { price, dutch_rebate })
+}
+
+// pays the mint price from the user's token account to the vault ATA, burning the configured share first
+fn pay_mint_price_in_token(accounts: &MintNft, payment_mint_key: Pubkey, mint_price: u64) -> Result<()> {
+    let (Some(payment_mint), Some(user_payment_account), Some(vault_payment_account), Some(payment_token_program)) = (
+        accounts.payment_mint.as_ref(),
+        accounts.user_payment_account.as_ref(),
+        accounts.vault_payment_account.as_ref(),
+        accounts.payment_token_program.as_ref(),
+    ) else {
+        return err!(MarketPlaceError::InvalidPaymentMint);
+    };
+    require_keys_eq!(payment_mint.key(), payment_mint_key, MarketPlaceError::InvalidPaymentMint);
+    require!(user_payment_account.amount >= mint_price, MarketPlaceError::InsufficientBalance);
+
+    let burned = marketplace::burn_payment_share(
+        &accounts.init,
+        payment_token_program,
+        payment_mint,
+        user_payment_account,
+        accounts.user.to_account_info(),
+        mint_price,
+    )?;
+
+    msg!("paying mint price to vault in tokens");
+
+    marketplace::transfer_payment(
+        payment_token_program,
+        payment_mint,
+        user_payment_account,
+        accounts.user.to_account_info(),
+        vault_payment_account.to_account_info(),
+        mint_price - burned,
+    )
+}
+
+// counts one more item against the max supply and returns its token number
+pub(crate) fn next_token_number(collection_info: &collection::CollectionInfo, token_count: &mut token_count::TokenCount) -> Result<u64> {
+    let max_supply = collection_info.max_supply;
+    require!(max_supply == 0 || token_count.count < max_supply, CollectionError::MaxSupplyReached);
+    token_count.count += 1;
+    Ok(token_count.count)
+}
+
+// counts the item against the max supply and charges the mint price, returns the token number
+pub(crate) fn charge_collection_mint(accounts: &mut MintNft, collection_id: u64, charge: MintCharge) -> Result<u64> {
+    let mint_price = charge.price;
+    let token_number = next_token_number(&accounts.collection_info, &mut accounts.token_count)?;
+
+    let wallet_mint = &mut accounts.wallet_mint;
+    wallet_mint.collection_id = collection_id;
+    wallet_mint.wallet = accounts.user.key();
+
+    if mint_price == 0 {
+        return Ok(token_number);
+    }
+
+    if let Some(payment_mint_key) = accounts.collection_info.payment_mint {
+        pay_mint_price_in_token(accounts, payment_mint_key, mint_price)?;
+        return Ok(token_number);
+    }
+
+    require!(accounts.user.lamports() >= mint_price, MarketPlaceError::InsufficientBalance);
 
+    // dutch auction rebates are paid back from the collection info, the rest is withdrawn to the vault later
+    let destination = if charge.dutch_rebate {
+        accounts.token_count.dutch_escrowed += mint_price;
+        accounts.collection_info.to_account_info()
+    } else {
+        accounts.vault.to_account_info()
+    };
+
+    msg!("paying mint price");
+
+    invoke(
+        &system_instruction::transfer(&accounts.user.key(), destination.key, mint_price),
+        &[
+            accounts.user.to_account_info(),
+            destination,
+            accounts.system_program.to_account_info(),
+        ],
+    )?;
+
+    Ok(token_number)
+}
+
+// accounts needed to mint one item, the payer funds the metadata and master edition rent
+pub(crate) struct CollectionItemAccounts<'info> {
+    pub payer: AccountInfo<'info>,
+    pub mint: AccountInfo<'info>,
+    pub token_account: AccountInfo<'info>,
+    pub metadata_account: AccountInfo<'info>,
+    pub master_edition: AccountInfo<'info>,
+    pub collection_mint: AccountInfo<'info>,
+    pub collection_metadata_account: AccountInfo<'info>,
+    pub collection_master_edition_account: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+    pub metadata_program: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+    pub rent: AccountInfo<'info>,
+}
+
+// mints the counted item, creates its metadata and master edition and verifies it in the collection
+pub(crate) fn create_collection_item<'info>(accounts: CollectionItemAccounts<'info>, collection_info: &collection::CollectionInfo, signer_seeds: &[&[&[u8]]], token_number: u64, name: String, symbol: String, uri: String) -> Result<()> {
     token::mint_to(CpiContext::new_with_signer(
-        ctx.accounts.token_program.to_account_info(),
+        accounts.token_program.clone(),
         MintTo{
-            mint: ctx.accounts.mint.to_account_info(),
-            to: ctx.accounts.nft_account_to.to_account_info(),
-            authority: ctx.accounts.collection_mint.to_account_info()
+            mint: accounts.mint.clone(),
+            to: accounts.token_account.clone(),
+            authority: accounts.collection_mint.clone()
         }, signer_seeds), 1)?;
 
     msg!("minted nft successfully and creating metadata account v3");
 
     metadata::create_metadata_accounts_v3(CpiContext::new_with_signer(
-        ctx.accounts.metadata_program.to_account_info(),
+        accounts.metadata_program.clone(),
         metadata::CreateMetadataAccountsV3{
-            metadata: ctx.accounts.metadata_account.to_account_info(),
-            mint: ctx.accounts.mint.to_account_info(),
-            mint_authority: ctx.accounts.collection_mint.to_account_info(),
-            update_authority: ctx.accounts.collection_mint.to_account_info(),
-            payer: ctx.accounts.user.to_account_info(),
-            system_program: ctx.accounts.system_program.to_account_info(),
-            rent: ctx.accounts.rent.to_account_info(),
+            metadata: accounts.metadata_account.clone(),
+            mint: accounts.mint.clone(),
+            mint_authority: accounts.collection_mint.clone(),
+            update_authority: accounts.collection_mint.clone(),
+            payer: accounts.payer.clone(),
+            system_program: accounts.system_program.clone(),
+            rent: accounts.rent.clone(),
         }, signer_seeds
     ), DataV2{
-        name: name,
-        symbol: symbol,
-        uri: uri,
-        seller_fee_basis_points: 0,
-        creators: None,
+        name,
+        symbol,
+        uri: collection_item_uri(collection_info, token_number, uri),
+        seller_fee_basis_points: collection_info.seller_fee_basis_points,
+        creators: Some(collection_creators(accounts.collection_mint.key(), &collection_info.creators)),
         collection: None,
-        uses: None,
+        uses: collection_uses(&collection_info.uses),
     }, true, true, None)?;
 
     msg!("minted nft created metadata successfully and creating master edtion account");
@@ -221,40 +498,81 @@ pub fn mint_nft(ctx: Context<MintNft>, name: String, symbol: String, uri: String
     // create master edition account for nft in collection
     metadata::create_master_edition_v3(
         CpiContext::new_with_signer(
-            ctx.accounts.metadata_program.to_account_info(),
+            accounts.metadata_program.clone(),
             CreateMasterEditionV3 {
-                payer: ctx.accounts.user.to_account_info(),
-                mint: ctx.accounts.mint.to_account_info(),
-                edition: ctx.accounts.master_edition.to_account_info(),
-                mint_authority: ctx.accounts.collection_mint.to_account_info(),
-                update_authority: ctx.accounts.collection_mint.to_account_info(),
-                metadata: ctx.accounts.metadata_account.to_account_info(),
-                token_program: ctx.accounts.token_program.to_account_info(),
-                system_program: ctx.accounts.system_program.to_account_info(),
-                rent: ctx.accounts.rent.to_account_info(),
+                payer: accounts.payer.clone(),
+                mint: accounts.mint.clone(),
+                edition: accounts.master_edition.clone(),
+                mint_authority: accounts.collection_mint.clone(),
+                update_authority: accounts.collection_mint.clone(),
+                metadata: accounts.metadata_account.clone(),
+                token_program: accounts.token_program.clone(),
+                system_program: accounts.system_program.clone(),
+                rent: accounts.rent.clone(),
             },
-            &signer_seeds,
+            signer_seeds,
         ),
-        Some(0),
+        Some(collection_info.edition_max_supply),
     )?;
 
     metadata::set_and_verify_sized_collection_item(CpiContext::new_with_signer(
-        ctx.accounts.metadata_program.to_account_info(),
+        accounts.metadata_program,
         SetAndVerifySizedCollectionItem  {
-            metadata: ctx.accounts.metadata_account.to_account_info(),
-            collection_authority: ctx.accounts.collection_mint.to_account_info(),
-            payer: ctx.accounts.user.to_account_info(),
-            update_authority: ctx.accounts.collection_mint.to_account_info(),
-            collection_mint: ctx.accounts.collection_mint.to_account_info(),
-            collection_metadata: ctx.accounts.collection_metadata_account.to_account_info(),
-            collection_master_edition: ctx.accounts.collection_master_edition_account.to_account_info(),
+            metadata: accounts.metadata_account,
+            collection_authority: accounts.collection_mint.clone(),
+            payer: accounts.payer,
+            update_authority: accounts.collection_mint.clone(),
+            collection_mint: accounts.collection_mint,
+            collection_metadata: accounts.collection_metadata_account,
+            collection_master_edition: accounts.collection_master_edition_account,
         }, signer_seeds
-    ), None)?;
+    ), None)
+}
+
+// shared by every mint path: counts, charges and mints one verified item of the collection
+fn mint_collection_item<'info>(accounts: &mut MintNft<'info>, collection_bump: u8, collection_id: u64, charge: MintCharge, name: String, symbol: String, uri: String) -> Result<()> {
+    msg!("minting !");
+    let collection_id_bytes = collection_id.to_le_bytes();
+    let signer_seeds: &[&[&[u8]]] = &[&[COLLECTION_SEED, &collection_id_bytes, &[collection_bump]]];
+
+    let token_number = charge_collection_mint(accounts, collection_id, charge)?;
+
+    create_collection_item(
+        CollectionItemAccounts {
+            payer: accounts.user.to_account_info(),
+            mint: accounts.mint.to_account_info(),
+            token_account: accounts.nft_account_to.to_account_info(),
+            metadata_account: accounts.metadata_account.to_account_info(),
+            master_edition: accounts.master_edition.to_account_info(),
+            collection_mint: accounts.collection_mint.to_account_info(),
+            collection_metadata_account: accounts.collection_metadata_account.to_account_info(),
+            collection_master_edition_account: accounts.collection_master_edition_account.to_account_info(),
+            token_program: accounts.token_program.to_account_info(),
+            metadata_program: accounts.metadata_program.to_account_info(),
+            system_program: accounts.system_program.to_account_info(),
+            rent: accounts.rent.to_account_info(),
+        },
+        &accounts.collection_info,
+        signer_seeds,
+        token_number,
+        name,
+        symbol,
+        uri,
+    )?;
+
+    emit!(MintNftEvent {
+        collection_id,
+        mint: accounts.mint.key(),
+        owner: accounts.user.key(),
+        token_number,
+        price: charge.price,
+    });
 
     Ok(())
 }
 
 #[derive(Accounts)]
+#[instruction(collection_id: u64)]
 pub struct MintNft<'info> {
     #[account(mut)]
     pub user: Signer<'info>,
@@ -262,11 +580,63 @@ pub struct MintNft<'info> {
     /// CHECK: address
     #[account(
         mut,
-        seeds = [COLLECTION_SEED],
+        seeds = [COLLECTION_SEED, collection_id.to_le_bytes().as_ref()],
         bump,
     )]
     pub collection_mint: Account<'info, Mint>,
 
+    #[account(
+        mut,
+        seeds = [COLLECTION_INFO, collection_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub collection_info: Box<Account<'info, collection::CollectionInfo>>,
+
+    #[account(
+        mut,
+        seeds = [TOKEN_COUNT_SEED, collection_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub token_count: Box<Account<'info, token_count::TokenCount>>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + wallet_mint::WalletMint::INIT_SPACE,
+        seeds = [WALLET_MINT_SEED, collection_id.to_le_bytes().as_ref(), user.key().as_ref()],
+        bump,
+    )]
+    pub wallet_mint: Box<Account<'info, wallet_mint::WalletMint>>,
+
+    #[account(
+        seeds = [INIT_SEED],
+        bump,
+        has_one = vault,
+    )]
+    pub init: Box<Account<'info, init::Init>>,
+
+    /// CHECK: address
+    #[account(mut)]
+    pub vault: UncheckedAccount<'info>,
+
+    // only needed when the collection is paid in an spl token
+    #[account(mut)]
+    pub payment_mint: Option<Box<InterfaceAccount<'info, token_interface::Mint>>>,
+
+    #[account(mut)]
+    pub user_payment_account: Option<Box<InterfaceAccount<'info, token_interface::TokenAccount>>>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = payment_mint,
+        associated_token::authority = vault,
+        associated_token::token_program = payment_token_program,
+    )]
+    pub vault_payment_account: Option<Box<InterfaceAccount<'info, token_interface::TokenAccount>>>,
+
+    pub payment_token_program: Option<Interface<'info, TokenInterface>>,
+
     #[account(
         init,
         payer = user,
@@ -319,14 +689,173 @@ pub struct MintNft<'info> {
     pub rent: Sysvar<'info, Rent>,
 }
 
+#[event]
+pub struct MintNftEvent {
+    pub collection_id: u64,
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub token_number: u64,
+    pub price: u64,
+}
+
+// message signed by the backend: collection_id | token_id | price | recipient | keccak(name) | keccak(symbol) | keccak(uri)
+pub fn voucher_message(collection_id: u64, token_id: u64, total_price: u64, recipient: &Pubkey, name: &str, symbol: &str, uri: &str) -> Vec<u8> {
+    let mut msg = Vec::with_capacity(8 + 8 + 8 + 32 + 32 * 3);
+    msg.extend_from_slice(&collection_id.to_le_bytes());
+    msg.extend_from_slice(&token_id.to_le_bytes());
+    msg.extend_from_slice(&total_price.to_le_bytes());
+    msg.extend_from_slice(recipient.as_ref());
+    msg.extend_from_slice(&keccak::hash(name.as_bytes()).to_bytes());
+    msg.extend_from_slice(&keccak::hash(symbol.as_bytes()).to_bytes());
+    msg.extend_from_slice(&keccak::hash(uri.as_bytes()).to_bytes());
+    msg
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn mint_nft_with_sig(ctx: Context<MintNftWithSig>, collection_id: u64, token_id: u64, total_price: u64, name: String, symbol: String, uri: String, sig: [u8; 64], recovery_id: u8) -> Result<()> {
+    let eth_address = ctx.accounts.mint_nft.collection_info.voucher_signer;
+    require!(eth_address != [0u8; 20], SigError::VoucherSignerNotSet);
+
+    // the voucher carries its own price and is not counted against phase wallet limits,
+    // but it is still only redeemable inside the schedule and never during a Closed phase
+    active_mint_phase(&ctx.accounts.mint_nft.collection_info, Clock::get()?.unix_timestamp)?;
+
+    let recipient = ctx.accounts.mint_nft.user.key();
+    let msg = voucher_message(collection_id, token_id, total_price, &recipient, &name, &symbol, &uri);
+
+    // Get what should be the Secp256k1Program instruction
+    let ix: Instruction = load_instruction_at_checked(0, &ctx.accounts.ix_sysvar)?;
+
+    // Check that ix is what we expect to have been sent
+    utils::verify_secp256k1_ix(&ix, &eth_address, &msg, &sig, recovery_id)?;
+
+    let used_voucher = &mut ctx.accounts.used_voucher;
+    used_voucher.collection_id = collection_id;
+    used_voucher.token_id = token_id;
+    used_voucher.mint = ctx.accounts.mint_nft.mint.key();
+    used_voucher.redeemer = recipient;
+
+    let charge = MintCharge { price: total_price, dutch_rebate: false };
+    mint_collection_item(&mut ctx.accounts.mint_nft, ctx.bumps.mint_nft.collection_mint, collection_id, charge, name, symbol, uri)?;
+
+    emit!(RedeemVoucherEvent {
+        collection_id,
+        token_id,
+        mint: ctx.accounts.mint_nft.mint.key(),
+        redeemer: recipient,
+        total_price,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(collection_id: u64, token_id: u64)]
+pub struct MintNftWithSig<'info> {
+    pub mint_nft: MintNft<'info>,
+
+    // creation fails if the voucher token_id was already redeemed
+    #[account(
+        init,
+        payer = mint_nft.user,
+        space = 8 + voucher::UsedVoucher::INIT_SPACE,
+        seeds = [VOUCHER_SEED, collection_id.to_le_bytes().as_ref(), token_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub used_voucher: Box<Account<'info, voucher::UsedVoucher>>,
+
+    /// CHECK: ix_sysvar
+    #[account(address = IX_ID)]
+    pub ix_sysvar: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event]
+pub struct RedeemVoucherEvent {
+    pub collection_id: u64,
+    pub token_id: u64,
+    pub mint: Pubkey,
+    pub redeemer: Pubkey,
+    pub total_price: u64,
+}
+
+// message signed by the allowlist key: wallet | collection_mint | max_quantity | expiry
+pub fn allowlist_message(wallet: &Pubkey, collection_mint: &Pubkey, max_quantity: u64, expiry: i64) -> Vec<u8> {
+    let mut msg = Vec::with_capacity(32 + 32 + 8 + 8);
+    msg.extend_from_slice(wallet.as_ref());
+    msg.extend_from_slice(collection_mint.as_ref());
+    msg.extend_from_slice(&max_quantity.to_le_bytes());
+    msg.extend_from_slice(&expiry.to_le_bytes());
+    msg
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn mint_nft_with_allowlist_voucher(ctx: Context<MintNftWithAllowlistVoucher>, collection_id: u64, max_quantity: u64, expiry: i64, name: String, symbol: String, uri: String, sig: [u8; 64]) -> Result<()> {
+    let signer = ctx.accounts.mint_nft.collection_info.allowlist_signer;
+    require!(signer != Pubkey::default(), SigError::VoucherSignerNotSet);
+    require!(Clock::get()?.unix_timestamp <= expiry, SigError::VoucherExpired);
+
+    let wallet = ctx.accounts.mint_nft.user.key();
+    let msg = allowlist_message(&wallet, &ctx.accounts.mint_nft.collection_mint.key(), max_quantity, expiry);
+
+    // Get what should be the Ed25519Program instruction
+    let ix: Instruction = load_instruction_at_checked(0, &ctx.accounts.ix_sysvar)?;
+
+    // Check that ix is what we expect to have been sent
+    let is_verified = utils::verify_ed25519_ix(&ix, signer.as_ref(), &msg, &sig)?;
+    require!(is_verified, SigError::SigVerificationFailed);
+
+    let charge = check_mint_phase(&mut ctx.accounts.mint_nft, collection::PhaseKind::Allowlist)?;
+
+    let wallet_mint = &mut ctx.accounts.mint_nft.wallet_mint;
+    require!(wallet_mint.allowlist_minted < max_quantity, SigError::VoucherQuantityExceeded);
+    wallet_mint.allowlist_minted += 1;
+
+    mint_collection_item(&mut ctx.accounts.mint_nft, ctx.bumps.mint_nft.collection_mint, collection_id, charge, name, symbol, uri)
+}
+
+#[derive(Accounts)]
+#[instruction(collection_id: u64)]
+pub struct MintNftWithAllowlistVoucher<'info> {
+    pub mint_nft: MintNft<'info>,
+
+    /// CHECK: ix_sysvar
+    #[account(address = IX_ID)]
+    pub ix_sysvar: AccountInfo<'info>,
+}
+
+pub fn mint_nft_with_proof(ctx: Context<MintNftWithProof>, collection_id: u64, allocation: u64, proof: Vec<[u8; 32]>, name: String, symbol: String, uri: String) -> Result<()> {
+    let merkle_root = ctx.accounts.mint_nft.collection_info.merkle_root;
+    require!(merkle_root != [0u8; 32], CollectionError::MerkleRootNotSet);
+
+    let wallet = ctx.accounts.mint_nft.user.key();
+    let leaf = keccak::hashv(&[wallet.as_ref(), &allocation.to_le_bytes()]).to_bytes();
+    require!(utils::verify_merkle_proof(&proof, &merkle_root, leaf), CollectionError::InvalidMerkleProof);
+
+    let charge = check_mint_phase(&mut ctx.accounts.mint_nft, collection::PhaseKind::Allowlist)?;
+
+    let wallet_mint = &mut ctx.accounts.mint_nft.wallet_mint;
+    require!(wallet_mint.merkle_minted < allocation, CollectionError::AllocationExceeded);
+    wallet_mint.merkle_minted += 1;
+
+    mint_collection_item(&mut ctx.accounts.mint_nft, ctx.bumps.mint_nft.collection_mint, collection_id, charge, name, symbol, uri)
+}
+
+#[derive(Accounts)]
+#[instruction(collection_id: u64)]
+pub struct MintNftWithProof<'info> {
+    pub mint_nft: MintNft<'info>,
+}
+
 pub fn create_token(ctx: Context<CreateToken>, decimals: u8, name: String, symbol: String, uri: String) -> Result<()>{
     let signer_seeds: &[&[&[u8]]] = &[&[TOKEN_SEED, &[ctx.bumps.mint]]];
 
     msg!("creating metadata account");
     let data_v2 = DataV2{
-        name: name,
-        symbol: symbol,
-        uri: uri,
+        name,
+        symbol,
+        uri,
         seller_fee_basis_points: 0,
         creators: Some(vec![Creator{
             address: ctx.accounts.admin.key(),
@@ -409,14 +938,164 @@ pub struct CreateToken<'info> {
     pub system_program: Program<'info, System>,
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct TransferFeeArgs {
+    pub transfer_fee_basis_points: u16,
+    pub maximum_fee: u64,
+}
+
+pub fn create_token_2022(ctx: Context<CreateToken2022>, decimals: u8, name: String, symbol: String, uri: String, transfer_fee: Option<TransferFeeArgs>, interest_rate: Option<i16>) -> Result<()> {
+    let signer_seeds: &[&[&[u8]]] = &[&[TOKEN_SEED, &[ctx.bumps.mint]]];
+    let mint_key = ctx.accounts.mint.key();
+
+    let mut extensions = vec![ExtensionType::MetadataPointer];
+    if transfer_fee.is_some() {
+        extensions.push(ExtensionType::TransferFeeConfig);
+    }
+    if interest_rate.is_some() {
+        extensions.push(ExtensionType::InterestBearingConfig);
+    }
+    let space = token_interface::find_mint_account_size(Some(&extensions))?;
+
+    // the metadata extension is reallocated by the token program, so fund it upfront
+    let token_metadata = TokenMetadata {
+        update_authority: OptionalNonZeroPubkey::try_from(Some(mint_key))?,
+        mint: mint_key,
+        name: name.clone(),
+        symbol: symbol.clone(),
+        uri: uri.clone(),
+        additional_metadata: vec![],
+    };
+    let lamports = ctx.accounts.rent.minimum_balance(space + token_metadata.tlv_size_of()?);
+
+    msg!("creating token 2022 mint account");
+
+    system_program::create_account(
+        CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            CreateAccount {
+                from: ctx.accounts.admin.to_account_info(),
+                to: ctx.accounts.mint.to_account_info(),
+            },
+            signer_seeds
+        ),
+        lamports,
+        space as u64,
+        &ctx.accounts.token_program.key(),
+    )?;
+
+    token_interface::metadata_pointer_initialize(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            token_interface::MetadataPointerInitialize {
+                token_program_id: ctx.accounts.token_program.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+            }
+        ),
+        Some(mint_key),
+        Some(mint_key),
+    )?;
+
+    if let Some(transfer_fee) = transfer_fee {
+        // withheld fees can be withdrawn by the init vault
+        token_interface::transfer_fee_initialize(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                token_interface::TransferFeeInitialize {
+                    token_program_id: ctx.accounts.token_program.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                }
+            ),
+            Some(&mint_key),
+            Some(&ctx.accounts.init.vault),
+            transfer_fee.transfer_fee_basis_points,
+            transfer_fee.maximum_fee,
+        )?;
+    }
+
+    if let Some(interest_rate) = interest_rate {
+        token_interface::interest_bearing_mint_initialize(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                token_interface::InterestBearingMintInitialize {
+                    token_program_id: ctx.accounts.token_program.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                }
+            ),
+            Some(mint_key),
+            interest_rate,
+        )?;
+    }
+
+    token_interface::initialize_mint2(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            token_interface::InitializeMint2 {
+                mint: ctx.accounts.mint.to_account_info(),
+            }
+        ),
+        decimals,
+        &mint_key,
+        Some(&mint_key),
+    )?;
+
+    msg!("creating token metadata extension");
+
+    token_interface::token_metadata_initialize(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token_interface::TokenMetadataInitialize {
+                token_program_id: ctx.accounts.token_program.to_account_info(),
+                metadata: ctx.accounts.mint.to_account_info(),
+                update_authority: ctx.accounts.mint.to_account_info(),
+                mint_authority: ctx.accounts.mint.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+            },
+            signer_seeds
+        ),
+        name,
+        symbol,
+        uri,
+    )?;
+
+    msg!("token 2022 mint created successfully!");
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CreateToken2022<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [INIT_SEED],
+        bump,
+        has_one = admin
+    )]
+    pub init: Box<Account<'info, init::Init>>,
+
+    /// CHECK: created and initialized in the instruction
+    #[account(
+        mut,
+        seeds = [TOKEN_SEED],
+        bump,
+    )]
+    pub mint: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token2022>,
+    pub rent: Sysvar<'info, Rent>,
+    pub system_program: Program<'info, System>,
+}
+
 pub fn mint_token(ctx: Context<MintToken>, amount: u64) -> Result<()> {
     let signer_seeds: &[&[&[u8]]] = &[&[TOKEN_SEED, &[ctx.bumps.mint]]];
 
     msg!("minting !");
 
-    token::mint_to(CpiContext::new_with_signer(
+    token_interface::mint_to(CpiContext::new_with_signer(
         ctx.accounts.token_program.to_account_info(),
-        MintTo {
+        token_interface::MintTo {
             mint: ctx.accounts.mint.to_account_info(),
             to: ctx.accounts.token_account_to.to_account_info(),
             authority: ctx.accounts.mint.to_account_info(),
@@ -426,7 +1105,7 @@ pub fn mint_token(ctx: Context<MintToken>, amount: u64) -> Result<()> {
     emit!(MintTokenEvent{
         mint: ctx.accounts.mint.to_account_info().key(),
         token_account_to: ctx.accounts.token_account_to.to_account_info().key(),
-        amount: amount
+        amount
     });
 
     Ok(())
@@ -449,8 +1128,9 @@ pub struct MintToken<'info> {
         mut,
         seeds = [TOKEN_SEED],
         bump,
+        mint::token_program = token_program,
     )]
-    pub mint: Box<Account<'info, Mint>>,
+    pub mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
 
     /// CHECK: address
     #[account(mut)]
@@ -461,10 +1141,11 @@ pub struct MintToken<'info> {
         payer = admin,
         associated_token::mint = mint, // mint account
         associated_token::authority = to, // wallet account
+        associated_token::token_program = token_program,
     )]
-    pub token_account_to: Box<Account<'info, TokenAccount>>,
+    pub token_account_to: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
 
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub rent: Sysvar<'info, Rent>,
     pub system_program: Program<'info, System>,
@@ -482,10 +1163,10 @@ pub fn revoke_mint_authority(ctx: Context<RevokeMintAuthority>) -> Result<()> {
 
     msg!("Revoking mint authority");
 
-    token::set_authority(
+    token_interface::set_authority(
         CpiContext::new_with_signer(
             ctx.accounts.token_program.to_account_info(),
-            SetAuthority {
+            token_interface::SetAuthority {
                 account_or_mint: ctx.accounts.mint.to_account_info(),
                 current_authority: ctx.accounts.mint.to_account_info(),
             },
@@ -516,20 +1197,21 @@ pub struct RevokeMintAuthority<'info> {
         mut,
         seeds = [TOKEN_SEED],
         bump,
+        mint::token_program = token_program,
     )]
-    pub mint: Box<Account<'info, Mint>>,
+    pub mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
 
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
 }
 
 pub fn burn_token(ctx: Context<BurnToken>, amount: u64) -> Result<()> {
     msg!("Burning tokens");
 
-    token::burn(
+    token_interface::burn(
         CpiContext::new(
             ctx.accounts.token_program.to_account_info(),
-            Burn {
+            token_interface::Burn {
                 mint: ctx.accounts.mint.to_account_info(),
                 from: ctx.accounts.token_account.to_account_info(),
                 authority: ctx.accounts.owner.to_account_info(),
@@ -543,7 +1225,7 @@ pub fn burn_token(ctx: Context<BurnToken>, amount: u64) -> Result<()> {
     emit!(BurnTokenEvent {
         mint: ctx.accounts.mint.to_account_info().key(),
         token_account: ctx.accounts.token_account.to_account_info().key(),
-        amount: amount
+        amount
     });
 
     Ok(())
@@ -557,17 +1239,19 @@ pub struct BurnToken<'info> {
         mut,
         seeds = [TOKEN_SEED],
         bump,
+        mint::token_program = token_program,
     )]
-    pub mint: Box<Account<'info, Mint>>,
+    pub mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
 
     #[account(
         mut,
         associated_token::mint = mint,
         associated_token::authority = owner,
+        associated_token::token_program = token_program,
     )]
-    pub token_account: Box<Account<'info, TokenAccount>>,
+    pub token_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
 
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
 }
@@ -593,3 +1277,99 @@ pub struct BurnTokenEvent {
 
 
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use collection::{MintPhase, PhaseKind};
+
+    fn phase(kind: PhaseKind, start_time: i64, end_time: i64, price: u64) -> MintPhase {
+        MintPhase {
+            kind,
+            start_time,
+            end_time,
+            price,
+            wallet_limit: 0,
+        }
+    }
+
+    fn collection_info(phases: Vec<MintPhase>) -> collection::CollectionInfo {
+        collection::CollectionInfo {
+            id: 1,
+            mint: Pubkey::new_unique(),
+            name: String::from("collection"),
+            creator: Pubkey::new_unique(),
+            authority: Pubkey::new_unique(),
+            max_supply: 0,
+            mint_price: 0,
+            voucher_signer: [0u8; 20],
+            allowlist_signer: Pubkey::default(),
+            merkle_root: [0u8; 32],
+            phases,
+            phases_version: 0,
+            seller_fee_basis_points: 0,
+            creators: Vec::new(),
+            edition_max_supply: 0,
+            rule_set: None,
+            uses: None,
+            game_authority: Pubkey::default(),
+            reveal: None,
+            dutch_auction: None,
+            payment_mint: None,
+        }
+    }
+
+    fn active_index(collection_info: &collection::CollectionInfo, now: i64) -> Result<Option<usize>> {
+        Ok(active_mint_phase(collection_info, now)?.map(|(index, _)| index))
+    }
+
+    #[test]
+    fn empty_schedule_has_no_active_phase() {
+        assert_eq!(active_index(&collection_info(Vec::new()), 0).unwrap(), None);
+    }
+
+    #[test]
+    fn phase_includes_its_start_and_excludes_its_end() {
+        let collection_info = collection_info(vec![
+            phase(PhaseKind::Allowlist, 100, 200, 1),
+            phase(PhaseKind::Public, 200, 300, 2),
+        ]);
+
+        assert_eq!(active_index(&collection_info, 100).unwrap(), Some(0));
+        assert_eq!(active_index(&collection_info, 199).unwrap(), Some(0));
+        assert_eq!(active_index(&collection_info, 200).unwrap(), Some(1));
+        assert_eq!(active_index(&collection_info, 299).unwrap(), Some(1));
+    }
+
+    #[test]
+    fn outside_every_phase_is_rejected() {
+        let collection_info = collection_info(vec![
+            phase(PhaseKind::Allowlist, 100, 200, 1),
+            phase(PhaseKind::Public, 300, 400, 2),
+        ]);
+
+        for now in [99, 200, 250, 400] {
+            assert_eq!(active_index(&collection_info, now).err(), Some(CollectionError::MintPhaseNotActive.into()));
+        }
+    }
+
+    #[test]
+    fn closed_phase_is_rejected() {
+        let collection_info = collection_info(vec![phase(PhaseKind::Closed, 100, 200, 0)]);
+
+        assert_eq!(active_index(&collection_info, 150).err(), Some(CollectionError::MintClosed.into()));
+    }
+
+    #[test]
+    fn overlapping_phases_resolve_to_the_first_one() {
+        let collection_info = collection_info(vec![
+            phase(PhaseKind::Allowlist, 100, 300, 1),
+            phase(PhaseKind::Public, 200, 400, 2),
+        ]);
+
+        let (index, phase) = active_mint_phase(&collection_info, 250).unwrap().unwrap();
+        assert_eq!(index, 0);
+        assert_eq!(phase.price, 1);
+        assert_eq!(active_index(&collection_info, 300).unwrap(), Some(1));
+    }
+}
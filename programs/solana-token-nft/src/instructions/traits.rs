@@ -0,0 +1,126 @@
+use anchor_lang::prelude::*;
+use anchor_spl::metadata::MetadataAccount;
+use anchor_spl::token::Mint;
+use crate::states::{collection, nft_traits};
+use crate::constants::{COLLECTION_INFO, COLLECTION_SEED, TRAITS_SEED};
+use crate::errors::CollectionError;
+use crate::instructions::spl_token::find_metadata_account;
+
+pub fn init_nft_traits(ctx: Context<InitNftTraits>, collection_id: u64, position: u8, rarity: nft_traits::Rarity) -> Result<()> {
+    let traits = &mut ctx.accounts.traits;
+    traits.collection_id = collection_id;
+    traits.mint = ctx.accounts.mint.key();
+    traits.level = 1;
+    traits.xp = 0;
+    traits.position = position;
+    traits.rarity = rarity;
+
+    emit!(NftTraitsEvent {
+        collection_id,
+        mint: traits.mint,
+        level: traits.level,
+        xp: traits.xp,
+        position: traits.position,
+        rarity: traits.rarity,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(collection_id: u64)]
+pub struct InitNftTraits<'info> {
+    #[account(mut)]
+    pub game_authority: Signer<'info>,
+
+    #[account(
+        seeds = [COLLECTION_INFO, collection_id.to_le_bytes().as_ref()],
+        bump,
+        has_one = game_authority @ CollectionError::Unauthorized,
+    )]
+    pub collection_info: Box<Account<'info, collection::CollectionInfo>>,
+
+    #[account(
+        seeds = [COLLECTION_SEED, collection_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub collection_mint: Box<Account<'info, Mint>>,
+
+    pub mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        address = find_metadata_account(&mint.key()).0,
+        constraint = metadata_account.collection.as_ref().is_some_and(|c| c.verified && c.key == collection_mint.key()) @ CollectionError::InvalidCollection,
+    )]
+    pub metadata_account: Box<Account<'info, MetadataAccount>>,
+
+    #[account(
+        init,
+        payer = game_authority,
+        space = 8 + nft_traits::NftTraits::INIT_SPACE,
+        seeds = [TRAITS_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub traits: Box<Account<'info, nft_traits::NftTraits>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn update_nft_traits(ctx: Context<UpdateNftTraits>, collection_id: u64, level: Option<u32>, xp: Option<u64>, position: Option<u8>, rarity: Option<nft_traits::Rarity>) -> Result<()> {
+    let traits = &mut ctx.accounts.traits;
+    if let Some(level) = level {
+        traits.level = level;
+    }
+    if let Some(xp) = xp {
+        traits.xp = xp;
+    }
+    if let Some(position) = position {
+        traits.position = position;
+    }
+    if let Some(rarity) = rarity {
+        traits.rarity = rarity;
+    }
+
+    emit!(NftTraitsEvent {
+        collection_id,
+        mint: traits.mint,
+        level: traits.level,
+        xp: traits.xp,
+        position: traits.position,
+        rarity: traits.rarity,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(collection_id: u64)]
+pub struct UpdateNftTraits<'info> {
+    pub game_authority: Signer<'info>,
+
+    #[account(
+        seeds = [COLLECTION_INFO, collection_id.to_le_bytes().as_ref()],
+        bump,
+        has_one = game_authority @ CollectionError::Unauthorized,
+    )]
+    pub collection_info: Box<Account<'info, collection::CollectionInfo>>,
+
+    #[account(
+        mut,
+        seeds = [TRAITS_SEED, traits.mint.as_ref()],
+        bump,
+        constraint = traits.collection_id == collection_id @ CollectionError::InvalidCollection,
+    )]
+    pub traits: Box<Account<'info, nft_traits::NftTraits>>,
+}
+
+// emitted with the full trait state on every change
+#[event]
+pub struct NftTraitsEvent {
+    pub collection_id: u64,
+    pub mint: Pubkey,
+    pub level: u32,
+    pub xp: u64,
+    pub position: u8,
+    pub rarity: nft_traits::Rarity,
+}
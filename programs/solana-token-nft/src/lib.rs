@@ -1,5 +1,6 @@
 use anchor_lang::prelude::*;
 use instructions::*;
+use states::{CreatorShare, DutchAuction, ItemUses, MintPhase, Rarity, RevealConfig};
 
 pub mod instructions;
 pub mod constants;
@@ -17,18 +18,213 @@ pub mod soleague_marketplace {
         spl_token::initialize(ctx, vault)
     }
 
-    pub fn create_collection(ctx: Context<CreateCollection>, name: String, symbol: String, uri: String) -> Result<()> {
-        spl_token::create_collection(ctx, name, symbol, uri)
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_collection(ctx: Context<CreateCollection>, collection_id: u64, authority: Pubkey, max_supply: u64, mint_price: u64, seller_fee_basis_points: u16, creators: Vec<CreatorShare>, name: String, symbol: String, uri: String, reveal: Option<RevealConfig>) -> Result<()> {
+        spl_token::create_collection(ctx, collection_id, authority, max_supply, mint_price, seller_fee_basis_points, creators, name, symbol, uri, reveal)
     }
 
-    pub fn mint_nft(ctx: Context<MintNft>, name: String, symbol: String, uri: String) -> Result<()> {
-        spl_token::mint_nft(ctx, name, symbol, uri)
+    pub fn set_max_supply(ctx: Context<SetMaxSupply>, collection_id: u64, max_supply: u64) -> Result<()> {
+        collection_config::set_max_supply(ctx, collection_id, max_supply)
+    }
+
+    pub fn set_mint_price(ctx: Context<SetMintPrice>, collection_id: u64, mint_price: u64) -> Result<()> {
+        collection_config::set_mint_price(ctx, collection_id, mint_price)
+    }
+
+    pub fn set_voucher_signer(ctx: Context<SetVoucherSigner>, collection_id: u64, eth_address: [u8; 20]) -> Result<()> {
+        collection_config::set_voucher_signer(ctx, collection_id, eth_address)
+    }
+
+    pub fn set_allowlist_signer(ctx: Context<SetAllowlistSigner>, collection_id: u64, signer: Pubkey) -> Result<()> {
+        collection_config::set_allowlist_signer(ctx, collection_id, signer)
+    }
+
+    pub fn set_merkle_root(ctx: Context<SetMerkleRoot>, collection_id: u64, merkle_root: [u8; 32]) -> Result<()> {
+        collection_config::set_merkle_root(ctx, collection_id, merkle_root)
+    }
+
+    pub fn set_mint_phases(ctx: Context<SetMintPhases>, collection_id: u64, phases: Vec<MintPhase>) -> Result<()> {
+        collection_config::set_mint_phases(ctx, collection_id, phases)
+    }
+
+    pub fn set_royalties(ctx: Context<SetRoyalties>, collection_id: u64, seller_fee_basis_points: u16, creators: Vec<CreatorShare>) -> Result<()> {
+        collection_config::set_royalties(ctx, collection_id, seller_fee_basis_points, creators)
+    }
+
+    pub fn set_edition_max_supply(ctx: Context<SetEditionMaxSupply>, collection_id: u64, edition_max_supply: u64) -> Result<()> {
+        collection_config::set_edition_max_supply(ctx, collection_id, edition_max_supply)
+    }
+
+    pub fn set_rule_set(ctx: Context<SetRuleSet>, collection_id: u64, rule_set: Option<Pubkey>) -> Result<()> {
+        collection_config::set_rule_set(ctx, collection_id, rule_set)
+    }
+
+    pub fn set_uses(ctx: Context<SetUses>, collection_id: u64, uses: Option<ItemUses>) -> Result<()> {
+        collection_config::set_uses(ctx, collection_id, uses)
+    }
+
+    pub fn set_game_authority(ctx: Context<SetGameAuthority>, collection_id: u64, game_authority: Pubkey) -> Result<()> {
+        collection_config::set_game_authority(ctx, collection_id, game_authority)
+    }
+
+    pub fn set_dutch_auction(ctx: Context<SetDutchAuction>, collection_id: u64, dutch_auction: Option<DutchAuction>) -> Result<()> {
+        collection_config::set_dutch_auction(ctx, collection_id, dutch_auction)
+    }
+
+    pub fn set_payment_mint(ctx: Context<SetPaymentMint>, collection_id: u64, payment_mint: Option<Pubkey>) -> Result<()> {
+        collection_config::set_payment_mint(ctx, collection_id, payment_mint)
+    }
+
+    pub fn mint_nft(ctx: Context<MintNft>, collection_id: u64, name: String, symbol: String, uri: String) -> Result<()> {
+        spl_token::mint_nft(ctx, collection_id, name, symbol, uri)
+    }
+
+    pub fn claim_dutch_rebate(ctx: Context<ClaimDutchRebate>, collection_id: u64) -> Result<()> {
+        dutch_auction::claim_dutch_rebate(ctx, collection_id)
+    }
+
+    pub fn withdraw_dutch_proceeds(ctx: Context<WithdrawDutchProceeds>, collection_id: u64) -> Result<()> {
+        dutch_auction::withdraw_dutch_proceeds(ctx, collection_id)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn mint_nft_with_sig(ctx: Context<MintNftWithSig>, collection_id: u64, token_id: u64, total_price: u64, name: String, symbol: String, uri: String, sig: [u8; 64], recovery_id: u8) -> Result<()> {
+        spl_token::mint_nft_with_sig(ctx, collection_id, token_id, total_price, name, symbol, uri, sig, recovery_id)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn mint_nft_with_allowlist_voucher(ctx: Context<MintNftWithAllowlistVoucher>, collection_id: u64, max_quantity: u64, expiry: i64, name: String, symbol: String, uri: String, sig: [u8; 64]) -> Result<()> {
+        spl_token::mint_nft_with_allowlist_voucher(ctx, collection_id, max_quantity, expiry, name, symbol, uri, sig)
+    }
+
+    pub fn mint_nft_with_proof(ctx: Context<MintNftWithProof>, collection_id: u64, allocation: u64, proof: Vec<[u8; 32]>, name: String, symbol: String, uri: String) -> Result<()> {
+        spl_token::mint_nft_with_proof(ctx, collection_id, allocation, proof, name, symbol, uri)
+    }
+
+    pub fn batch_mint_nft<'info>(ctx: Context<'_, '_, 'info, 'info, BatchMintNft<'info>>, collection_id: u64, symbol: String, items: Vec<BatchMintItem>) -> Result<()> {
+        batch_mint::batch_mint_nft(ctx, collection_id, symbol, items)
+    }
+
+    pub fn update_nft_metadata(ctx: Context<UpdateNftMetadata>, collection_id: u64, name: Option<String>, symbol: Option<String>, uri: Option<String>, seller_fee_basis_points: Option<u16>, is_mutable: Option<bool>) -> Result<()> {
+        nft::update_nft_metadata(ctx, collection_id, name, symbol, uri, seller_fee_basis_points, is_mutable)
+    }
+
+    pub fn burn_nft(ctx: Context<BurnNftItem>, collection_id: u64) -> Result<()> {
+        nft::burn_nft(ctx, collection_id)
+    }
+
+    pub fn utilize_nft(ctx: Context<UtilizeNft>, collection_id: u64) -> Result<()> {
+        nft::utilize_nft(ctx, collection_id)
+    }
+
+    pub fn freeze_nft(ctx: Context<FreezeNft>, collection_id: u64) -> Result<()> {
+        nft::freeze_nft(ctx, collection_id)
+    }
+
+    pub fn thaw_nft(ctx: Context<FreezeNft>, collection_id: u64) -> Result<()> {
+        nft::thaw_nft(ctx, collection_id)
+    }
+
+    pub fn print_edition(ctx: Context<PrintEdition>, collection_id: u64) -> Result<()> {
+        nft::print_edition(ctx, collection_id)
+    }
+
+    pub fn reveal(ctx: Context<Reveal>, collection_id: u64, uri: String, proof: Vec<[u8; 32]>) -> Result<()> {
+        nft::reveal(ctx, collection_id, uri, proof)
+    }
+
+    pub fn init_nft_traits(ctx: Context<InitNftTraits>, collection_id: u64, position: u8, rarity: Rarity) -> Result<()> {
+        traits::init_nft_traits(ctx, collection_id, position, rarity)
+    }
+
+    pub fn update_nft_traits(ctx: Context<UpdateNftTraits>, collection_id: u64, level: Option<u32>, xp: Option<u64>, position: Option<u8>, rarity: Option<Rarity>) -> Result<()> {
+        traits::update_nft_traits(ctx, collection_id, level, xp, position, rarity)
+    }
+
+    pub fn mint_pnft(ctx: Context<MintPnft>, collection_id: u64, name: String, symbol: String, uri: String) -> Result<()> {
+        pnft::mint_pnft(ctx, collection_id, name, symbol, uri)
+    }
+
+    pub fn transfer_pnft(ctx: Context<TransferPnft>) -> Result<()> {
+        pnft::transfer_pnft(ctx)
+    }
+
+    pub fn delegate_pnft(ctx: Context<DelegatePnft>, kind: PnftDelegateKind) -> Result<()> {
+        pnft::delegate_pnft(ctx, kind)
+    }
+
+    pub fn revoke_pnft_delegate(ctx: Context<DelegatePnft>, kind: PnftDelegateKind) -> Result<()> {
+        pnft::revoke_pnft_delegate(ctx, kind)
+    }
+
+    pub fn set_platform_fee(ctx: Context<SetPlatformFee>, fee_basis_points: u16) -> Result<()> {
+        marketplace::set_platform_fee(ctx, fee_basis_points)
+    }
+
+    pub fn set_payment_burn(ctx: Context<SetPlatformFee>, burn_basis_points: u16) -> Result<()> {
+        marketplace::set_payment_burn(ctx, burn_basis_points)
+    }
+
+    pub fn list_item(ctx: Context<ListItem>, collection_id: u64, price: u64, payment_mint: Option<Pubkey>) -> Result<()> {
+        marketplace::list_item(ctx, collection_id, price, payment_mint)
+    }
+
+    pub fn buy_item_with_token<'info>(ctx: Context<'_, '_, 'info, 'info, BuyItemWithToken<'info>>, price: u64) -> Result<()> {
+        marketplace::buy_item_with_token(ctx, price)
+    }
+
+    pub fn cancel_listing(ctx: Context<CancelListing>) -> Result<()> {
+        marketplace::cancel_listing(ctx)
+    }
+
+    pub fn buy_item<'info>(ctx: Context<'_, '_, 'info, 'info, BuyItem<'info>>, price: u64) -> Result<()> {
+        marketplace::buy_item(ctx, price)
+    }
+
+    pub fn make_offer(ctx: Context<MakeOffer>, collection_id: u64, amount: u64) -> Result<()> {
+        offer::make_offer(ctx, collection_id, amount)
+    }
+
+    pub fn cancel_offer(ctx: Context<CancelOffer>) -> Result<()> {
+        offer::cancel_offer(ctx)
+    }
+
+    pub fn accept_offer<'info>(ctx: Context<'_, '_, 'info, 'info, AcceptOffer<'info>>) -> Result<()> {
+        offer::accept_offer(ctx)
+    }
+
+    pub fn make_collection_bid(ctx: Context<MakeCollectionBid>, collection_id: u64, price: u64, quantity: u64) -> Result<()> {
+        offer::make_collection_bid(ctx, collection_id, price, quantity)
+    }
+
+    pub fn cancel_collection_bid(ctx: Context<CancelCollectionBid>) -> Result<()> {
+        offer::cancel_collection_bid(ctx)
+    }
+
+    pub fn sell_into_collection_bid<'info>(ctx: Context<'_, '_, 'info, 'info, SellIntoCollectionBid<'info>>, collection_id: u64) -> Result<()> {
+        offer::sell_into_collection_bid(ctx, collection_id)
+    }
+
+    pub fn create_auction(ctx: Context<CreateAuction>, collection_id: u64, reserve_price: u64, min_increment: u64, end_time: i64) -> Result<()> {
+        auction::create_auction(ctx, collection_id, reserve_price, min_increment, end_time)
+    }
+
+    pub fn place_bid(ctx: Context<PlaceBid>, amount: u64) -> Result<()> {
+        auction::place_bid(ctx, amount)
+    }
+
+    pub fn settle_auction<'info>(ctx: Context<'_, '_, 'info, 'info, SettleAuction<'info>>) -> Result<()> {
+        auction::settle_auction(ctx)
     }
 
     pub fn create_token(ctx: Context<CreateToken>, decimals: u8, name: String, symbol: String, uri: String) -> Result<()> {
         spl_token::create_token(ctx, decimals, name, symbol, uri)
     }
 
+    pub fn create_token_2022(ctx: Context<CreateToken2022>, decimals: u8, name: String, symbol: String, uri: String, transfer_fee: Option<TransferFeeArgs>, interest_rate: Option<i16>) -> Result<()> {
+        spl_token::create_token_2022(ctx, decimals, name, symbol, uri, transfer_fee, interest_rate)
+    }
+
     pub fn mint_token(ctx: Context<MintToken>, amount: u64) -> Result<()> {
         spl_token::mint_token(ctx, amount)
     }
@@ -41,10 +237,6 @@ pub mod soleague_marketplace {
         spl_token::revoke_mint_authority(ctx)
     }
 
-//     pub fn mint_nft_with_sig(ctx: Context<MintNft>, token_id: u64, total_price:u64, name: String, symbol: String, uri: String, eth_address: [u8; 20], sig: [u8; 64], recovery_id: u8) -> Result<()> {
-//         spl_token::mint_nft_with_sig(ctx, token_id, total_price, name, symbol, uri, eth_address, sig, recovery_id)
-//     }
-//
 //     pub fn verify_ed25519(ctx: Context<Verify>,  pubkey: Vec<u8>, msg: Vec<u8>, sig: Vec<u8>) -> Result<bool> {
 //         signature::verify_ed25519(ctx, pubkey, msg, sig)
 //     }
@@ -0,0 +1,17 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace)]
+pub struct Auction {
+    pub collection_id: u64,
+    pub mint: Pubkey,
+    pub seller: Pubkey,
+    pub reserve_price: u64,
+    pub min_increment: u64,
+    // unix timestamp, pushed back by late bids
+    pub end_time: i64,
+    // default pubkey until the first bid
+    pub top_bidder: Pubkey,
+    // lamports escrowed in this account on top of its rent
+    pub top_bid: u64,
+}
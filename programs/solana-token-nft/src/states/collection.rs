@@ -0,0 +1,104 @@
+use anchor_lang::prelude::*;
+use crate::constants::{MAX_CREATORS, MAX_MINT_PHASES};
+
+#[account]
+#[derive(InitSpace)]
+pub struct CollectionInfo {
+    pub id: u64,
+    pub mint: Pubkey,
+    #[max_len(32)]
+    pub name: String,
+    pub creator: Pubkey,
+    pub authority: Pubkey,
+    // 0 means the collection has no supply cap
+    pub max_supply: u64,
+    // charged per mint and sent to the init vault, in lamports or payment_mint base units
+    pub mint_price: u64,
+    // eth address of the backend key signing secp256k1 mint vouchers
+    pub voucher_signer: [u8; 20],
+    // ed25519 key signing allowlist vouchers
+    pub allowlist_signer: Pubkey,
+    // root of keccak(wallet | allocation) leaves
+    pub merkle_root: [u8; 32],
+    // empty schedule keeps minting always open at mint_price
+    #[max_len(MAX_MINT_PHASES)]
+    pub phases: Vec<MintPhase>,
+    // bumped on every schedule change so wallets restart their per-phase counters
+    pub phases_version: u64,
+    pub seller_fee_basis_points: u16,
+    // the collection mint PDA is always added as a verified creator with 0 share
+    #[max_len(MAX_CREATORS)]
+    pub creators: Vec<CreatorShare>,
+    // prints allowed from each minted master edition, 0 disables printing
+    pub edition_max_supply: u64,
+    // token auth rule set applied to programmable nfts
+    pub rule_set: Option<Pubkey>,
+    // metaplex uses written into every minted nft, None mints plain nfts
+    pub uses: Option<ItemUses>,
+    // game server key allowed to spend uses
+    pub game_authority: Pubkey,
+    // committed at creation, None mints with the caller's uri right away
+    pub reveal: Option<RevealConfig>,
+    // descending public price, overrides the phase and mint price when set
+    pub dutch_auction: Option<DutchAuction>,
+    // spl token the mint price is paid in, None pays in lamports
+    pub payment_mint: Option<Pubkey>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace)]
+pub struct CreatorShare {
+    pub address: Pubkey,
+    pub share: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum PhaseKind {
+    Allowlist,
+    Public,
+    Closed,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace)]
+pub struct MintPhase {
+    pub kind: PhaseKind,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub price: u64,
+    // 0 means no per-wallet cap
+    pub wallet_limit: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum ItemUseMethod {
+    Burn,
+    Multiple,
+    Single,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace)]
+pub struct ItemUses {
+    pub use_method: ItemUseMethod,
+    pub total: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct RevealConfig {
+    // sha256 merkle root of sha256(token_number | uri) over the ordered metadata list
+    pub provenance_hash: [u8; 32],
+    // items are minted with placeholder_uri + token_number + ".json" until revealed
+    #[max_len(180)]
+    pub placeholder_uri: String,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace)]
+pub struct DutchAuction {
+    pub start_time: i64,
+    pub start_price: u64,
+    pub floor_price: u64,
+    // seconds between two price drops
+    pub decay_interval: i64,
+    // taken off the price at every interval
+    pub step: u64,
+    // escrow mint payments so early buyers can claim back the difference to the clearing price
+    pub rebate: bool,
+}
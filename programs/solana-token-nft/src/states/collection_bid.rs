@@ -0,0 +1,12 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace)]
+pub struct CollectionBid {
+    pub collection_id: u64,
+    pub bidder: Pubkey,
+    // lamports paid per item
+    pub price: u64,
+    // items still wanted, price * quantity is escrowed in this account on top of its rent
+    pub quantity: u64,
+}
@@ -4,4 +4,8 @@ use anchor_lang::prelude::*;
 pub struct Init{
     pub admin: Pubkey,
     pub vault: Pubkey,
+    // platform fee taken on marketplace sales, fits in the space left over by initialize
+    pub fee_basis_points: u16,
+    // share of every payment made with the program token that is burned
+    pub burn_basis_points: u16,
 }
\ No newline at end of file
@@ -0,0 +1,14 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace)]
+pub struct Item {
+    pub collection_id: u64,
+    pub mint: Pubkey,
+    pub seller: Pubkey,
+    // asked in lamports, or in payment_mint base units when set
+    pub price: u64,
+    pub payment_mint: Option<Pubkey>,
+    // false once the listing is canceled or sold, the account is reused on relist
+    pub listed: bool,
+}
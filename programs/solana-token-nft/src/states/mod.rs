@@ -1,2 +1,29 @@
 pub mod init;
 pub use init::*;
+
+pub mod collection;
+pub use collection::*;
+
+pub mod token_count;
+pub use token_count::*;
+
+pub mod voucher;
+pub use voucher::*;
+
+pub mod wallet_mint;
+pub use wallet_mint::*;
+
+pub mod nft_traits;
+pub use nft_traits::*;
+
+pub mod item;
+pub use item::*;
+
+pub mod offer;
+pub use offer::*;
+
+pub mod auction;
+pub use auction::*;
+
+pub mod collection_bid;
+pub use collection_bid::*;
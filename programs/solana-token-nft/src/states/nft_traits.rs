@@ -0,0 +1,22 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace)]
+pub struct NftTraits {
+    pub collection_id: u64,
+    pub mint: Pubkey,
+    pub level: u32,
+    pub xp: u64,
+    // roster position id defined by the game server
+    pub position: u8,
+    pub rarity: Rarity,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum Rarity {
+    Common,
+    Uncommon,
+    Rare,
+    Epic,
+    Legendary,
+}
@@ -0,0 +1,11 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace)]
+pub struct Offer {
+    pub collection_id: u64,
+    pub mint: Pubkey,
+    pub bidder: Pubkey,
+    // lamports escrowed in this account on top of its rent
+    pub amount: u64,
+}
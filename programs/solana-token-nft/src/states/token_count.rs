@@ -0,0 +1,19 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace)]
+pub struct TokenCount {
+    pub count: u64,
+    pub burned: u64,
+    // dutch auction accounting, payments are escrowed in the collection info while rebates are on
+    pub dutch_minted: u64,
+    pub dutch_paid: u64,
+    pub dutch_last_price: u64,
+    pub dutch_escrowed: u64,
+    pub dutch_withdrawn: u64,
+    pub dutch_rebated: u64,
+    // frozen by the first claim or withdrawal once the drop ended
+    pub dutch_clearing_price: u64,
+    // bumped when a settled drop is replaced, wallets from older drops restart their dutch counters
+    pub dutch_drop: u64,
+}
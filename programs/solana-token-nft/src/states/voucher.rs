@@ -0,0 +1,10 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace)]
+pub struct UsedVoucher {
+    pub collection_id: u64,
+    pub token_id: u64,
+    pub mint: Pubkey,
+    pub redeemer: Pubkey,
+}
@@ -0,0 +1,20 @@
+use anchor_lang::prelude::*;
+use crate::constants::MAX_MINT_PHASES;
+
+#[account]
+#[derive(InitSpace)]
+pub struct WalletMint {
+    pub collection_id: u64,
+    pub wallet: Pubkey,
+    pub allowlist_minted: u64,
+    pub merkle_minted: u64,
+    // minted per phase, indexed like CollectionInfo.phases
+    pub phase_minted: [u64; MAX_MINT_PHASES],
+    // CollectionInfo.phases_version the counters above belong to
+    pub phases_version: u64,
+    pub dutch_minted: u64,
+    pub dutch_paid: u64,
+    pub rebate_claimed: bool,
+    // TokenCount.dutch_drop the dutch counters above belong to
+    pub dutch_drop: u64,
+}
@@ -64,9 +64,6 @@ pub fn check_ed25519_data(data: &[u8], pubkey: &[u8], msg: &[u8], sig: &[u8]) ->
     {
         return Err(SigError::SigVerificationFailed.into());
     }
-    msg!("Debug: data_pubkey = {:?}", data_pubkey );
-    msg!("Debug: data_msg = {:?}", data_msg );
-    msg!("Debug: data_sig = {:?}", data_sig );
     // Arguments
     if  data_pubkey != pubkey   ||
         data_msg    != msg      ||
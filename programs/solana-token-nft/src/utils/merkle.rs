@@ -0,0 +1,104 @@
+use solana_program::{hash, keccak};
+
+fn verify_sorted_pairs(proof: &[[u8; 32]], root: &[u8; 32], leaf: [u8; 32], hashv: fn(&[&[u8]]) -> [u8; 32]) -> bool {
+    let mut computed = leaf;
+
+    for node in proof.iter() {
+        computed = if computed <= *node {
+            hashv(&[&computed, node])
+        } else {
+            hashv(&[node, &computed])
+        };
+    }
+
+    computed == *root
+}
+
+/// Verify a keccak256 Merkle proof built with sorted pairs
+pub fn verify_merkle_proof(proof: &[[u8; 32]], root: &[u8; 32], leaf: [u8; 32]) -> bool {
+    verify_sorted_pairs(proof, root, leaf, |values| keccak::hashv(values).to_bytes())
+}
+
+/// Verify a sha256 Merkle proof built with sorted pairs
+pub fn verify_sha256_merkle_proof(proof: &[[u8; 32]], root: &[u8; 32], leaf: [u8; 32]) -> bool {
+    verify_sorted_pairs(proof, root, leaf, |values| hash::hashv(values).to_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keccak_pair(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+        if a <= b {
+            keccak::hashv(&[a, b]).to_bytes()
+        } else {
+            keccak::hashv(&[b, a]).to_bytes()
+        }
+    }
+
+    fn sha256_pair(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+        if a <= b {
+            hash::hashv(&[a, b]).to_bytes()
+        } else {
+            hash::hashv(&[b, a]).to_bytes()
+        }
+    }
+
+    type Tree = ([[u8; 32]; 4], [u8; 32], Vec<Vec<[u8; 32]>>);
+
+    // four leaf tree, returns the leaves, the root and the proof of every leaf
+    fn tree(pair: fn(&[u8; 32], &[u8; 32]) -> [u8; 32]) -> Tree {
+        let leaves = [[1u8; 32], [2u8; 32], [3u8; 32], [4u8; 32]];
+        let left = pair(&leaves[0], &leaves[1]);
+        let right = pair(&leaves[2], &leaves[3]);
+        let root = pair(&left, &right);
+        let proofs = vec![
+            vec![leaves[1], right],
+            vec![leaves[0], right],
+            vec![leaves[3], left],
+            vec![leaves[2], left],
+        ];
+
+        (leaves, root, proofs)
+    }
+
+    #[test]
+    fn valid_proofs_verify() {
+        let (leaves, root, proofs) = tree(keccak_pair);
+        for (leaf, proof) in leaves.iter().zip(proofs.iter()) {
+            assert!(verify_merkle_proof(proof, &root, *leaf));
+        }
+
+        let (leaves, root, proofs) = tree(sha256_pair);
+        for (leaf, proof) in leaves.iter().zip(proofs.iter()) {
+            assert!(verify_sha256_merkle_proof(proof, &root, *leaf));
+        }
+    }
+
+    #[test]
+    fn wrong_leaf_is_rejected() {
+        let (leaves, root, proofs) = tree(keccak_pair);
+        assert!(!verify_merkle_proof(&proofs[0], &root, leaves[2]));
+        assert!(!verify_merkle_proof(&proofs[0], &root, [9u8; 32]));
+
+        let (leaves, root, proofs) = tree(sha256_pair);
+        assert!(!verify_sha256_merkle_proof(&proofs[0], &root, leaves[2]));
+    }
+
+    #[test]
+    fn empty_proof_only_matches_a_single_leaf_root() {
+        let (leaves, root, _) = tree(keccak_pair);
+        assert!(!verify_merkle_proof(&[], &root, leaves[0]));
+        assert!(verify_merkle_proof(&[], &leaves[0], leaves[0]));
+        assert!(verify_sha256_merkle_proof(&[], &leaves[0], leaves[0]));
+    }
+
+    #[test]
+    fn hash_functions_are_not_interchangeable() {
+        let (leaves, root, proofs) = tree(keccak_pair);
+        assert!(!verify_sha256_merkle_proof(&proofs[0], &root, leaves[0]));
+
+        let (leaves, root, proofs) = tree(sha256_pair);
+        assert!(!verify_merkle_proof(&proofs[0], &root, leaves[0]));
+    }
+}
@@ -5,6 +5,8 @@
 
 pub mod ed25519;
 pub mod secp256k1;
+pub mod merkle;
 
 pub use ed25519::*;
-pub use secp256k1::*;
\ No newline at end of file
+pub use secp256k1::*;
+pub use merkle::*;
\ No newline at end of file